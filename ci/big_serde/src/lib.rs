@@ -10,10 +10,13 @@
 
 #![cfg(test)]
 
-use num_bigint::{BigInt, BigUint};
+use num_bigint::{BigInt, BigUint, MaxBits};
 use num_traits::{One, Zero};
-use serde::{de::DeserializeOwned, Serialize};
-use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
+use serde::de::{DeserializeOwned, DeserializeSeed};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Token,
+};
 use std::{fmt::Debug, panic::catch_unwind};
 
 #[test]
@@ -161,3 +164,90 @@ fn bad_size_hint<T: Debug + DeserializeOwned + One + PartialEq + Serialize>(
     catch_unwind(|| assert_ser_tokens(&T::one(), &tokens)).unwrap_err();
     assert_de_tokens(&T::one(), &tokens);
 }
+
+/// Deserializes through `MaxBits` with a limit of 40 bits.
+#[derive(Debug, PartialEq)]
+struct Bounded<T>(T);
+
+impl<'de, T> Deserialize<'de> for Bounded<T>
+where
+    MaxBits<T>: DeserializeSeed<'de, Value = T>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MaxBits::new(40).deserialize(deserializer).map(Bounded)
+    }
+}
+
+#[test]
+fn max_bits_uint() {
+    let n = BigUint::from((1u64 << 40) - 1);
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::U32(u32::MAX),
+        Token::U32(0xff),
+        Token::SeqEnd,
+    ];
+    assert_de_tokens(&Bounded(n), &tokens);
+
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::U32(0),
+        Token::U32(0x100),
+        Token::SeqEnd,
+    ];
+    assert_de_tokens_error::<Bounded<BigUint>>(
+        &tokens,
+        "invalid value: sequence, expected a sequence of unsigned 32-bit numbers of at most 40 bits",
+    );
+
+    let tokens = [
+        Token::Seq { len: Some(3) },
+        Token::U32(0),
+        Token::U32(0),
+        Token::U32(1),
+        Token::SeqEnd,
+    ];
+    assert_de_tokens_error::<Bounded<BigUint>>(
+        &tokens,
+        "invalid length 3, expected a sequence of unsigned 32-bit numbers of at most 40 bits",
+    );
+
+    // Excess digits are rejected as they arrive, even if they're zero.
+    let tokens = [
+        Token::Seq { len: None },
+        Token::U32(1),
+        Token::U32(0),
+        Token::U32(0),
+    ];
+    assert_de_tokens_error::<Bounded<BigUint>>(
+        &tokens,
+        "invalid length 3, expected a sequence of unsigned 32-bit numbers of at most 40 bits",
+    );
+}
+
+#[test]
+fn max_bits_int() {
+    let tokens = [
+        Token::Tuple { len: 2 },
+        Token::I8(-1),
+        Token::Seq { len: Some(2) },
+        Token::U32(0),
+        Token::U32(1),
+        Token::SeqEnd,
+        Token::TupleEnd,
+    ];
+    assert_de_tokens(&Bounded(-(BigInt::one() << 32u32)), &tokens);
+
+    let tokens = [
+        Token::Tuple { len: 2 },
+        Token::I8(1),
+        Token::Seq { len: Some(3) },
+        Token::U32(0),
+        Token::U32(0),
+        Token::U32(1),
+    ];
+    assert_de_tokens_error::<Bounded<BigInt>>(
+        &tokens,
+        "invalid length 3, expected a sequence of unsigned 32-bit numbers of at most 40 bits",
+    );
+}
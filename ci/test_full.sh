@@ -27,8 +27,8 @@ if ! check_version $MSRV ; then
   exit 1
 fi
 
-STD_FEATURES=(arbitrary quickcheck rand serde)
-NO_STD_FEATURES=(serde rand)
+STD_FEATURES=(arbitrary cbor quickcheck rand serde)
+NO_STD_FEATURES=(cbor serde rand)
 echo "Testing supported features: ${STD_FEATURES[*]}"
 if [ -n "${NO_STD_FEATURES[*]}" ]; then
   echo " no_std supported features: ${NO_STD_FEATURES[*]}"
@@ -55,6 +55,12 @@ done
 cargo build --no-default-features --features="std ${STD_FEATURES[*]}"
 cargo test --no-default-features --features="std ${STD_FEATURES[*]}"
 
+# test each digit size, regardless of the target pointer width
+for feature in u32-digit u64-digit; do
+  cargo build --features="$feature"
+  cargo test --features="$feature"
+done
+
 
 if [ -n "${NO_STD_FEATURES[*]}" ]; then
   # test minimal `no_std`
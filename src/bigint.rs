@@ -7,7 +7,7 @@ use core::cmp::Ordering::{self, Equal};
 use core::default::Default;
 use core::fmt;
 use core::hash;
-use core::ops::{Neg, Not};
+use core::ops::{Neg, Not, Range};
 use core::str;
 
 use num_integer::{Integer, Roots};
@@ -15,9 +15,16 @@ use num_traits::{ConstZero, Num, One, Pow, Signed, Zero};
 
 use self::Sign::{Minus, NoSign, Plus};
 
-use crate::big_digit::BigDigit;
+use crate::big_digit::{self, BigDigit};
 use crate::biguint::to_str_radix_reversed;
-use crate::biguint::{BigUint, IntDigits, U32Digits, U64Digits};
+use crate::biguint::{BigUint, DigitVec, IntDigits, U32Digits, U64Digits};
+use crate::encoding::{der, leb128, ssh};
+use crate::format;
+use crate::Sign;
+use crate::{
+    AllocBigIntError, BigFormat, DecodeBigIntError, LiteralOptions, ParseBigIntError,
+    TryFromBigIntError,
+};
 
 mod addition;
 mod division;
@@ -30,28 +37,9 @@ mod convert;
 mod power;
 mod serde;
 mod shift;
+mod view;
 
-/// A `Sign` is a [`BigInt`]'s composing element.
-#[derive(PartialEq, PartialOrd, Eq, Ord, Copy, Clone, Debug, Hash)]
-pub enum Sign {
-    Minus,
-    NoSign,
-    Plus,
-}
-
-impl Neg for Sign {
-    type Output = Self;
-
-    /// Negate `Sign` value.
-    #[inline]
-    fn neg(self) -> Self {
-        match self {
-            Minus => Plus,
-            NoSign => NoSign,
-            Plus => Minus,
-        }
-    }
-}
+pub use self::view::BigIntRef;
 
 /// A big signed integer type.
 pub struct BigInt {
@@ -139,33 +127,31 @@ impl fmt::Debug for BigInt {
 
 impl fmt::Display for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(!self.is_negative(), "", &self.data.to_str_radix(10))
+        format::fmt_radix(f, !self.is_negative(), "", &self.data, 10, false)
     }
 }
 
 impl fmt::Binary for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(!self.is_negative(), "0b", &self.data.to_str_radix(2))
+        format::fmt_radix(f, !self.is_negative(), "0b", &self.data, 2, false)
     }
 }
 
 impl fmt::Octal for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(!self.is_negative(), "0o", &self.data.to_str_radix(8))
+        format::fmt_radix(f, !self.is_negative(), "0o", &self.data, 8, false)
     }
 }
 
 impl fmt::LowerHex for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(!self.is_negative(), "0x", &self.data.to_str_radix(16))
+        format::fmt_radix(f, !self.is_negative(), "0x", &self.data, 16, false)
     }
 }
 
 impl fmt::UpperHex for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut s = self.data.to_str_radix(16);
-        s.make_ascii_uppercase();
-        f.pad_integral(!self.is_negative(), "0x", &s)
+        format::fmt_radix(f, !self.is_negative(), "0x", &self.data, 16, true)
     }
 }
 
@@ -533,7 +519,7 @@ impl IntDigits for BigInt {
         self.data.digits()
     }
     #[inline]
-    fn digits_mut(&mut self) -> &mut Vec<BigDigit> {
+    fn digits_mut(&mut self) -> &mut DigitVec {
         self.data.digits_mut()
     }
     #[inline]
@@ -568,6 +554,54 @@ impl BigInt {
         data: BigUint::ZERO,
     };
 
+    /// Creates a [`BigInt`] from an `i64` in a const context, for constants and statics.
+    ///
+    /// For values too large for a primitive, see the [`bigint!`](crate::bigint!) macro.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// const MIN: BigInt = BigInt::from_i64_const(i64::MIN);
+    /// assert_eq!(MIN, BigInt::from(i64::MIN));
+    /// ```
+    #[inline]
+    pub const fn from_i64_const(n: i64) -> Self {
+        Self::from_i128_const(n as i128)
+    }
+
+    /// Creates a [`BigInt`] from an `i128` in a const context, for constants and statics.
+    #[inline]
+    pub const fn from_i128_const(n: i128) -> Self {
+        let sign = if n < 0 {
+            Minus
+        } else if n > 0 {
+            Plus
+        } else {
+            NoSign
+        };
+        BigInt {
+            sign,
+            data: BigUint::from_u128_const(n.unsigned_abs()),
+        }
+    }
+
+    /// Creates a [`BigInt`] from static digits without trailing zeros, in a const context.
+    pub(crate) const fn from_static_digits(negative: bool, digits: &'static [BigDigit]) -> Self {
+        let sign = if digits.is_empty() {
+            NoSign
+        } else if negative {
+            Minus
+        } else {
+            Plus
+        };
+        BigInt {
+            sign,
+            data: BigUint::from_static_digits(digits),
+        }
+    }
+
     /// Creates and initializes a [`BigInt`].
     ///
     /// The base 2<sup>32</sup> digits are ordered least significant digit first.
@@ -676,6 +710,78 @@ impl BigInt {
         Self::from_str_radix(s, radix).ok()
     }
 
+    /// Parses a string in the given `radix` like [`Num::from_str_radix`], but fails with
+    /// [`BigIntErrorKind::TooLong`][crate::BigIntErrorKind::TooLong] if the magnitude would
+    /// have more than `max_bits` bits.
+    ///
+    /// Inputs with too many significant digits are rejected before any conversion work, so
+    /// this is suitable for untrusted input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigIntErrorKind};
+    ///
+    /// assert_eq!(BigInt::from_str_radix_max_bits("-ff", 16, 8), Ok(BigInt::from(-255)));
+    /// let err = BigInt::from_str_radix_max_bits("-100", 16, 8).unwrap_err();
+    /// assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+    /// ```
+    pub fn from_str_radix_max_bits(
+        s: &str,
+        radix: u32,
+        max_bits: u64,
+    ) -> Result<Self, ParseBigIntError> {
+        if let Some(tail) = s.strip_prefix('-') {
+            if !tail.starts_with('+') {
+                let bu = BigUint::from_str_radix_max_bits(tail, radix, max_bits)
+                    .map_err(ParseBigIntError::after_sign)?;
+                return Ok(Self::from_biguint(Minus, bu));
+            }
+        }
+        let bu = BigUint::from_str_radix_max_bits(s, radix, max_bits)?;
+        Ok(Self::from_biguint(Plus, bu))
+    }
+
+    /// Parses an integer written like a Rust literal, with an optional `+` or `-` sign, an
+    /// optional `0b`, `0o`, or `0x` radix prefix, and `_` separators between digits.
+    ///
+    /// This is the same as [`parse_literal_with`][Self::parse_literal_with] using the default
+    /// [`LiteralOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// assert_eq!(BigInt::parse_literal("-0x_dead_beef"), Ok(BigInt::from(-0xdead_beef_i64)));
+    /// assert_eq!(BigInt::parse_literal("+0b1010"), Ok(BigInt::from(10)));
+    /// assert_eq!(BigInt::parse_literal("1_000_000"), Ok(BigInt::from(1_000_000)));
+    /// assert!(BigInt::parse_literal("0xg").is_err());
+    /// ```
+    pub fn parse_literal(s: &str) -> Result<Self, ParseBigIntError> {
+        Self::parse_literal_with(s, &LiteralOptions::new())
+    }
+
+    /// Parses an integer written like a Rust literal, as configured by `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigIntErrorKind, LiteralOptions};
+    ///
+    /// let strict = LiteralOptions::strict();
+    /// assert_eq!(BigInt::parse_literal_with("-0x10", &strict), Ok(BigInt::from(-16)));
+    ///
+    /// let err = BigInt::parse_literal_with("-0x1_0", &strict).unwrap_err();
+    /// assert_eq!(err.kind(), BigIntErrorKind::MisplacedUnderscore);
+    /// assert_eq!(err.position(), Some(4));
+    /// ```
+    pub fn parse_literal_with(s: &str, options: &LiteralOptions) -> Result<Self, ParseBigIntError> {
+        let (negative, n) = options.parse(s)?;
+        let sign = if negative { Minus } else { Plus };
+        Ok(Self::from_biguint(sign, n))
+    }
+
     /// Creates and initializes a [`BigInt`]. Each `u8` of the input slice is
     /// interpreted as one digit of the number
     /// and must therefore be less than `radix`.
@@ -856,6 +962,257 @@ impl BigInt {
         convert::to_signed_bytes_le(self)
     }
 
+    /// Returns the two's-complement byte representation of the [`BigInt`] in big-endian byte
+    /// order, sign-extended to exactly `width` bytes.
+    ///
+    /// Fails if the value doesn't fit in `width` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let i = BigInt::from(-1125);
+    /// assert_eq!(i.to_signed_bytes_be_padded(4).unwrap(), vec![255, 255, 251, 155]);
+    /// assert!(BigInt::from(128).to_signed_bytes_be_padded(1).is_err());
+    /// ```
+    pub fn to_signed_bytes_be_padded(
+        &self,
+        width: usize,
+    ) -> Result<Vec<u8>, TryFromBigIntError<()>> {
+        let mut bytes = vec![0; width];
+        self.write_signed_bytes_be(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Returns the two's-complement byte representation of the [`BigInt`] in little-endian byte
+    /// order, sign-extended to exactly `width` bytes.
+    ///
+    /// Fails if the value doesn't fit in `width` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let i = BigInt::from(-1125);
+    /// assert_eq!(i.to_signed_bytes_le_padded(4).unwrap(), vec![155, 251, 255, 255]);
+    /// ```
+    pub fn to_signed_bytes_le_padded(
+        &self,
+        width: usize,
+    ) -> Result<Vec<u8>, TryFromBigIntError<()>> {
+        let mut bytes = vec![0; width];
+        self.write_signed_bytes_le(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Writes the two's-complement byte representation of the [`BigInt`] in big-endian byte
+    /// order to fill `buf` exactly, sign-extending as needed.
+    ///
+    /// Fails without writing anything if the value needs more bytes than `buf` has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let mut buf = [0; 2];
+    /// BigInt::from(-128).write_signed_bytes_be(&mut buf[..1]).unwrap();
+    /// assert_eq!(buf, [0x80, 0]);
+    /// BigInt::from(-129).write_signed_bytes_be(&mut buf).unwrap();
+    /// assert_eq!(buf, [0xff, 0x7f]);
+    /// assert!(BigInt::from(-129).write_signed_bytes_be(&mut buf[..1]).is_err());
+    /// ```
+    pub fn write_signed_bytes_be(&self, buf: &mut [u8]) -> Result<(), TryFromBigIntError<()>> {
+        convert::write_signed_bytes_le(self, buf)?;
+        buf.reverse();
+        Ok(())
+    }
+
+    /// Writes the two's-complement byte representation of the [`BigInt`] in little-endian byte
+    /// order to fill `buf` exactly, sign-extending as needed.
+    ///
+    /// Fails without writing anything if the value needs more bytes than `buf` has.
+    pub fn write_signed_bytes_le(&self, buf: &mut [u8]) -> Result<(), TryFromBigIntError<()>> {
+        convert::write_signed_bytes_le(self, buf)
+    }
+
+    /// Returns the ASN.1 DER encoding of the [`BigInt`] as an `INTEGER`, including the tag and
+    /// length octets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// assert_eq!(BigInt::from(-129).to_der_integer(), vec![0x02, 0x02, 0xff, 0x7f]);
+    /// ```
+    pub fn to_der_integer(&self) -> Vec<u8> {
+        der::encode(&self.to_signed_bytes_be())
+    }
+
+    /// Decodes a [`BigInt`] from exactly one ASN.1 DER `INTEGER`, including the tag and length
+    /// octets.
+    ///
+    /// Encodings that are valid BER but not minimal DER are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, DecodeErrorKind};
+    ///
+    /// let i = BigInt::from_der_integer(&[0x02, 0x02, 0xff, 0x7f]).unwrap();
+    /// assert_eq!(i, BigInt::from(-129));
+    ///
+    /// let err = BigInt::from_der_integer(&[0x02, 0x02, 0xff, 0xff]).unwrap_err();
+    /// assert_eq!(err.kind(), DecodeErrorKind::NonMinimal);
+    /// ```
+    pub fn from_der_integer(bytes: &[u8]) -> Result<Self, DecodeBigIntError> {
+        der::decode(bytes).map(Self::from_signed_bytes_be)
+    }
+
+    /// Returns the SSH `mpint` encoding of the [`BigInt`]: a big-endian 32-bit length, then the
+    /// minimal two's-complement bytes, with zero having no bytes at all.
+    ///
+    /// Panics if the encoding would be longer than `u32::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// assert_eq!(BigInt::from(-0x1234).to_ssh_mpint(), vec![0, 0, 0, 2, 0xed, 0xcc]);
+    /// assert_eq!(BigInt::from(0).to_ssh_mpint(), vec![0, 0, 0, 0]);
+    /// ```
+    pub fn to_ssh_mpint(&self) -> Vec<u8> {
+        if self.is_zero() {
+            ssh::encode(&[])
+        } else {
+            ssh::encode(&self.to_signed_bytes_be())
+        }
+    }
+
+    /// Decodes a [`BigInt`] from an SSH `mpint` at the start of `bytes`, returning it along with
+    /// the rest of the input.
+    ///
+    /// Encodings with unnecessary leading `0x00` or `0xff` bytes are rejected. Use
+    /// [`from_ssh_mpint_lenient`][Self::from_ssh_mpint_lenient] to accept them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let (i, rest) = BigInt::from_ssh_mpint(&[0, 0, 0, 1, 0x80, 0xaa]).unwrap();
+    /// assert_eq!(i, BigInt::from(-128));
+    /// assert_eq!(rest, &[0xaa]);
+    ///
+    /// assert!(BigInt::from_ssh_mpint(&[0, 0, 0, 2, 0xff, 0x80]).is_err());
+    /// ```
+    pub fn from_ssh_mpint(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        let (content, rest) = ssh::decode(bytes, true)?;
+        Ok((Self::from_signed_bytes_be(content), rest))
+    }
+
+    /// Decodes a [`BigInt`] from an SSH `mpint` at the start of `bytes`, like
+    /// [`from_ssh_mpint`][Self::from_ssh_mpint], but accepting encodings that are not minimal.
+    pub fn from_ssh_mpint_lenient(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        let (content, rest) = ssh::decode(bytes, false)?;
+        Ok((Self::from_signed_bytes_be(content), rest))
+    }
+
+    /// Appends the signed LEB128 encoding of the [`BigInt`] to `out`, seven bits of its two's
+    /// complement per byte starting from the least significant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let mut out = Vec::new();
+    /// BigInt::from(-123456).write_sleb128(&mut out);
+    /// assert_eq!(out, [0xc0, 0xbb, 0x78]);
+    /// ```
+    pub fn write_sleb128(&self, out: &mut Vec<u8>) {
+        leb128::write_signed(self, out);
+    }
+
+    /// Decodes a [`BigInt`] from signed LEB128 at the start of `bytes`, returning it along with
+    /// the rest of the input. Redundant high groups of sign bits are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let (i, rest) = BigInt::read_sleb128(&[0xc0, 0xbb, 0x78]).unwrap();
+    /// assert_eq!(i, BigInt::from(-123456));
+    /// assert!(rest.is_empty());
+    /// ```
+    pub fn read_sleb128(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        leb128::read_signed(bytes)
+    }
+
+    /// Writes the signed LEB128 encoding of the [`BigInt`] to an [`io::Write`][std::io::Write].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_sleb128_io<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let mut out = Vec::new();
+        leb128::write_signed(self, &mut out);
+        writer.write_all(&out)
+    }
+
+    /// Reads a [`BigInt`] encoded in signed LEB128 from an [`io::Read`][std::io::Read].
+    ///
+    /// This reads one byte at a time, so that nothing after the encoded value is consumed. A
+    /// buffered reader is recommended.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_sleb128_io<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
+        leb128::read_signed_io(reader)
+    }
+
+    /// Maps the [`BigInt`] to a [`BigUint`] with zigzag encoding, interleaving non-negative and
+    /// negative values as `0, -1, 1, -2, 2, ...`, so that small magnitudes stay small. This is
+    /// commonly combined with unsigned LEB128, as in protobuf's `sint` types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigUint};
+    ///
+    /// assert_eq!(BigInt::from(-3).to_zigzag(), BigUint::from(5u32));
+    /// assert_eq!(BigInt::from(3).to_zigzag(), BigUint::from(6u32));
+    /// ```
+    pub fn to_zigzag(&self) -> BigUint {
+        let n = &self.data << 1u8;
+        if self.is_negative() {
+            n - 1u32
+        } else {
+            n
+        }
+    }
+
+    /// Maps a zigzag-encoded [`BigUint`] back to a [`BigInt`], reversing
+    /// [`to_zigzag`][Self::to_zigzag].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigUint};
+    ///
+    /// assert_eq!(BigInt::from_zigzag(&BigUint::from(5u32)), BigInt::from(-3));
+    /// ```
+    pub fn from_zigzag(n: &BigUint) -> Self {
+        let half = Self::from(n >> 1u8);
+        if n.bit(0) {
+            -half - 1
+        } else {
+            half
+        }
+    }
+
     /// Returns the integer formatted as a string in the given radix.
     /// `radix` must be in the range `2...36`.
     ///
@@ -879,6 +1236,56 @@ impl BigInt {
         unsafe { String::from_utf8_unchecked(v) }
     }
 
+    /// Returns a builder to format the integer with custom options, like digit grouping and
+    /// zero padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let i = BigInt::from(-1_000_000);
+    /// assert_eq!(i.format().group(3, ',').to_string(), "-1,000,000");
+    /// assert_eq!(i.format().min_digits(10).to_string(), "-0001000000");
+    /// ```
+    #[inline]
+    pub fn format(&self) -> BigFormat<'_> {
+        BigFormat::new(self.sign, &self.data)
+    }
+
+    /// Writes the integer formatted in the given radix, like [`to_str_radix`][Self::to_str_radix],
+    /// but without building the whole string in memory first.
+    /// `radix` must be in the range `2...36`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let i = BigInt::parse_bytes(b"-ff", 16).unwrap();
+    /// let mut s = String::new();
+    /// i.write_radix_to(&mut s, 8).unwrap();
+    /// assert_eq!(s, "-377");
+    /// ```
+    pub fn write_radix_to<W: fmt::Write + ?Sized>(&self, w: &mut W, radix: u32) -> fmt::Result {
+        format::write_radix(w, self.sign, &self.data, radix)
+    }
+
+    /// Writes the integer formatted in the given radix to an [`io::Write`][std::io::Write],
+    /// like [`to_str_radix`][Self::to_str_radix], but without building the whole string in
+    /// memory first. `radix` must be in the range `2...36`.
+    ///
+    /// The digits are written in pieces, so an unbuffered writer may see many small writes.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_radix_to_io<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        radix: u32,
+    ) -> std::io::Result<()> {
+        format::write_radix_io(w, self.sign, &self.data, radix)
+    }
+
     /// Returns the integer in the requested base in big-endian digit order.
     /// The output is not given in a human readable alphabet but as a zero
     /// based `u8` number.
@@ -974,6 +1381,66 @@ impl BigInt {
         self.data.bits()
     }
 
+    /// Creates a zero [`BigInt`] with room for magnitudes of at least `bits` bits without
+    /// reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let i = BigInt::with_capacity_bits(1000);
+    /// assert_eq!(i, BigInt::from(0));
+    /// assert!(i.capacity_bits() >= 1000);
+    /// ```
+    pub fn with_capacity_bits(bits: u64) -> Self {
+        BigInt {
+            sign: NoSign,
+            data: BigUint::with_capacity_bits(bits),
+        }
+    }
+
+    /// Returns the number of magnitude bits the [`BigInt`] can hold without reallocating.
+    #[inline]
+    pub fn capacity_bits(&self) -> u64 {
+        self.data.capacity_bits()
+    }
+
+    /// Reserves capacity for the magnitude of the [`BigInt`] to grow to at least `bits` bits
+    /// without reallocating. Does nothing if the capacity is already sufficient.
+    ///
+    /// Assignment operators like `+=` and `>>=` keep the capacity even when the value gets smaller,
+    /// until [`shrink_to_fit`][Self::shrink_to_fit] releases it.
+    #[inline]
+    pub fn reserve_bits(&mut self, bits: u64) {
+        self.data.reserve_bits(bits);
+    }
+
+    /// Shrinks the capacity of the [`BigInt`] as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Sets the [`BigInt`] to zero, keeping its allocated capacity for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let mut i = -(BigInt::from(1) << 1000u32);
+    /// let capacity = i.capacity_bits();
+    /// i.clear();
+    /// assert_eq!(i, BigInt::from(0));
+    /// assert_eq!(i.capacity_bits(), capacity);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.sign = NoSign;
+    }
+
     /// Converts this [`BigInt`] into a [`BigUint`], if it's not negative.
     #[inline]
     pub fn to_biguint(&self) -> Option<BigUint> {
@@ -1012,6 +1479,78 @@ impl BigInt {
         Pow::pow(self, exponent)
     }
 
+    /// Returns `self ^ exponent`, or an error instead of aborting if the result can't be
+    /// allocated. See [`BigUint::try_pow`].
+    pub fn try_pow(&self, exponent: u32) -> Result<Self, AllocBigIntError> {
+        let sign = if exponent.is_odd() { self.sign } else { Plus };
+        let data = self.data.try_pow(exponent)?;
+        Ok(BigInt::from_biguint(sign, data))
+    }
+
+    /// Returns `self * other`, or an error instead of aborting if the result can't be
+    /// allocated. See [`BigUint::try_mul`].
+    pub fn try_mul(&self, other: &Self) -> Result<Self, AllocBigIntError> {
+        let data = self.data.try_mul(&other.data)?;
+        Ok(BigInt::from_biguint(self.sign * other.sign, data))
+    }
+
+    /// Returns `self << bits`, or an error instead of aborting if the result can't be
+    /// allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// assert_eq!(BigInt::from(-3).try_shl(100), Ok(BigInt::from(-3) << 100u32));
+    /// assert!(BigInt::from(-3).try_shl(u64::MAX).is_err());
+    /// ```
+    pub fn try_shl(&self, bits: u64) -> Result<Self, AllocBigIntError> {
+        let data = self.data.try_shl(bits)?;
+        Ok(BigInt::from_biguint(self.sign, data))
+    }
+
+    /// Computes `self += a * b` in place.
+    ///
+    /// The product accumulates directly into `self` without being materialized, so loops like
+    /// dot products only allocate when `self` needs to grow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let big = BigInt::from(10u32).pow(30);
+    /// let xs = [&big + 1u32, -&big, BigInt::from(3)];
+    /// let ys = [&big - 1u32, &big + 2u32, BigInt::from(-5)];
+    ///
+    /// let mut dot = BigInt::default();
+    /// for (x, y) in xs.iter().zip(&ys) {
+    ///     dot.add_mul(x, y);
+    /// }
+    /// assert_eq!(dot, -(&big * 2u32) - 16u32);
+    /// ```
+    pub fn add_mul(&mut self, a: &Self, b: &Self) {
+        multiplication::add_mul(self, a.sign * b.sign, a.digits(), b.digits());
+    }
+
+    /// Computes `self -= a * b` in place. See [`BigInt::add_mul`].
+    pub fn sub_mul(&mut self, a: &Self, b: &Self) {
+        multiplication::add_mul(self, -(a.sign * b.sign), a.digits(), b.digits());
+    }
+
+    /// Computes `self += a * b` in place for a scalar `b`. See [`BigInt::add_mul`].
+    pub fn add_mul_digit(&mut self, a: &Self, b: u64) {
+        let (digits, len) = big_digit::from_u128(b.into());
+        multiplication::add_mul(self, a.sign, a.digits(), &digits[..len]);
+    }
+
+    /// Computes `self -= a * b` in place for a scalar `b`. See [`BigInt::add_mul`].
+    pub fn sub_mul_digit(&mut self, a: &Self, b: u64) {
+        let (digits, len) = big_digit::from_u128(b.into());
+        multiplication::add_mul(self, -a.sign, a.digits(), &digits[..len]);
+    }
+
     /// Returns `(self ^ exponent) mod modulus`
     ///
     /// Note that this rounds like `mod_floor`, not like the `%` operator,
@@ -1106,6 +1645,27 @@ impl BigInt {
         self.data.trailing_zeros()
     }
 
+    /// Returns the number of one bits among the lowest `width` bits,
+    /// using the two's complement for negative numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// assert_eq!(BigInt::from(0b1011).count_ones_in(64), 3);
+    /// assert_eq!(BigInt::from(-1).count_ones_in(64), 64);
+    /// assert_eq!(BigInt::from(-0x100).count_ones_in(16), 8);
+    /// ```
+    pub fn count_ones_in(&self, width: u64) -> u64 {
+        if self.is_negative() {
+            // -n is the complement of n - 1
+            (&self.data - 1u32).count_zeros(width)
+        } else {
+            width - self.data.count_zeros(width)
+        }
+    }
+
     /// Returns whether the bit in position `bit` is set,
     /// using the two's complement for negative numbers
     pub fn bit(&self, bit: u64) -> bool {
@@ -1152,6 +1712,144 @@ impl BigInt {
         // The top bit may have been cleared, so normalize
         self.normalize();
     }
+
+    /// Returns the bits in `range` of the two's-complement [`BigInt`], shifted down so that bit
+    /// `range.start` becomes bit 0. Bits past the end of the number are copies of the sign bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigUint};
+    ///
+    /// let i = BigInt::from(-0x100);
+    /// assert_eq!(i.extract_bits(4..12), BigUint::from(0xf0u32));
+    /// assert_eq!(i.extract_bits(64..72), BigUint::from(0xffu32));
+    /// ```
+    pub fn extract_bits(&self, range: Range<u64>) -> BigUint {
+        bits::extract_bits(self, range)
+    }
+
+    /// Replaces the bits in `range` of the two's-complement [`BigInt`] with the low bits of
+    /// `value`, ignoring any bits of `value` that don't fit in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigUint};
+    ///
+    /// let mut i = BigInt::from(-1);
+    /// i.deposit_bits(0..8, &BigUint::from(0x7fu32));
+    /// assert_eq!(i, BigInt::from(-0x81));
+    /// ```
+    pub fn deposit_bits(&mut self, range: Range<u64>, value: &BigUint) {
+        let width = range.end.saturating_sub(range.start);
+        bits::update_bits(
+            self,
+            range.clone(),
+            |x| x.deposit_bits(range.clone(), value),
+            |x| x.deposit_bits(range.clone(), &value.not_bits(width)),
+        );
+    }
+
+    /// Clears all of the bits in `range` of the two's-complement [`BigInt`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let mut i = BigInt::from(-1);
+    /// i.clear_bits(4..8);
+    /// assert_eq!(i, BigInt::from(-0xf1));
+    /// ```
+    pub fn clear_bits(&mut self, range: Range<u64>) {
+        bits::update_bits(
+            self,
+            range.clone(),
+            |x| x.clear_bits(range.clone()),
+            |x| x.set_bits(range.clone()),
+        );
+    }
+
+    /// Sets all of the bits in `range` of the two's-complement [`BigInt`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let mut i = BigInt::from(-0x100);
+    /// i.set_bits(0..4);
+    /// assert_eq!(i, BigInt::from(-0xf1));
+    /// ```
+    pub fn set_bits(&mut self, range: Range<u64>) {
+        bits::update_bits(
+            self,
+            range.clone(),
+            |x| x.set_bits(range.clone()),
+            |x| x.clear_bits(range.clone()),
+        );
+    }
+
+    /// Returns the `bits` least significant bits of the two's-complement [`BigInt`], which is
+    /// the same as reducing it modulo 2<sup>`bits`</sup> to a non-negative value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigUint};
+    ///
+    /// assert_eq!(BigInt::from(-1).to_unsigned_bits(8), BigUint::from(0xffu32));
+    /// assert_eq!(BigInt::from(0x1234).to_unsigned_bits(8), BigUint::from(0x34u32));
+    /// ```
+    pub fn to_unsigned_bits(&self, bits: u64) -> BigUint {
+        let mut n = self.data.clone();
+        if self.is_negative() {
+            n.negate_bits(bits);
+        } else {
+            n.truncate_bits(bits);
+        }
+        n
+    }
+
+    /// Wraps the [`BigInt`] into the range of a `bits`-wide two's-complement integer, like an
+    /// `as` cast between primitive integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// assert_eq!(BigInt::from(200).wrapping_to_bits(8), BigInt::from(200u8 as i8));
+    /// assert_eq!(BigInt::from(-129).wrapping_to_bits(8), BigInt::from(127));
+    /// ```
+    pub fn wrapping_to_bits(&self, bits: u64) -> BigInt {
+        self.to_unsigned_bits(bits).to_signed_from_bits(bits)
+    }
+
+    /// Returns `self + other`, wrapped into the range of a `bits`-wide two's-complement integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let a = BigInt::from(100);
+    /// assert_eq!(a.wrapping_add_bits(&BigInt::from(100), 8), BigInt::from(-56));
+    /// ```
+    pub fn wrapping_add_bits(&self, other: &BigInt, bits: u64) -> BigInt {
+        (self + other).wrapping_to_bits(bits)
+    }
+
+    /// Returns `self - other`, wrapped into the range of a `bits`-wide two's-complement integer.
+    pub fn wrapping_sub_bits(&self, other: &BigInt, bits: u64) -> BigInt {
+        (self - other).wrapping_to_bits(bits)
+    }
+
+    /// Returns `self * other`, wrapped into the range of a `bits`-wide two's-complement integer.
+    pub fn wrapping_mul_bits(&self, other: &BigInt, bits: u64) -> BigInt {
+        (self * other).wrapping_to_bits(bits)
+    }
 }
 
 impl num_traits::FromBytes for BigInt {
@@ -17,14 +17,13 @@ macro_rules! bigint_add {
     ($a:expr, $a_owned:expr, $a_data:expr, $b:expr, $b_owned:expr, $b_data:expr) => {
         match ($a.sign, $b.sign) {
             (_, NoSign) => $a_owned,
-            (NoSign, _) => $b_owned,
+            (NoSign, _) => BigInt::from_biguint($b.sign, $a_data + $b_data),
             // same sign => keep the sign with the sum of magnitudes
             (Plus, Plus) | (Minus, Minus) => BigInt::from_biguint($a.sign, $a_data + $b_data),
             // opposite signs => keep the sign of the larger with the difference of magnitudes
             (Plus, Minus) | (Minus, Plus) => match $a.data.cmp(&$b.data) {
                 Less => BigInt::from_biguint($b.sign, $b_data - $a_data),
-                Greater => BigInt::from_biguint($a.sign, $a_data - $b_data),
-                Equal => BigInt::ZERO,
+                Greater | Equal => BigInt::from_biguint($a.sign, $a_data - $b_data),
             },
         }
     };
@@ -94,12 +93,10 @@ impl Add<u32> for BigInt {
     #[inline]
     fn add(self, other: u32) -> Self {
         match self.sign {
-            NoSign => From::from(other),
-            Plus => Self::from(self.data + other),
+            NoSign | Plus => Self::from(self.data + other),
             Minus => match self.data.cmp(&From::from(other)) {
-                Equal => Self::ZERO,
                 Less => Self::from(other - self.data),
-                Greater => -Self::from(self.data - other),
+                Greater | Equal => -Self::from(self.data - other),
             },
         }
     }
@@ -119,12 +116,10 @@ impl Add<u64> for BigInt {
     #[inline]
     fn add(self, other: u64) -> Self {
         match self.sign {
-            NoSign => From::from(other),
-            Plus => Self::from(self.data + other),
+            NoSign | Plus => Self::from(self.data + other),
             Minus => match self.data.cmp(&From::from(other)) {
-                Equal => Self::ZERO,
                 Less => Self::from(other - self.data),
-                Greater => -Self::from(self.data - other),
+                Greater | Equal => -Self::from(self.data - other),
             },
         }
     }
@@ -144,12 +139,10 @@ impl Add<u128> for BigInt {
     #[inline]
     fn add(self, other: u128) -> Self {
         match self.sign {
-            NoSign => Self::from(other),
-            Plus => Self::from(self.data + other),
+            NoSign | Plus => Self::from(self.data + other),
             Minus => match self.data.cmp(&From::from(other)) {
-                Equal => Self::ZERO,
                 Less => Self::from(other - self.data),
-                Greater => -Self::from(self.data - other),
+                Greater | Equal => -Self::from(self.data - other),
             },
         }
     }
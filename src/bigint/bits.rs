@@ -2,11 +2,11 @@ use super::BigInt;
 use super::Sign::{Minus, NoSign, Plus};
 
 use crate::big_digit::{self, BigDigit, DoubleBigDigit};
-use crate::biguint::IntDigits;
+use crate::biguint::{BigUint, DigitVec, IntDigits};
+use crate::{IsizePromotion, UsizePromotion};
 
-use alloc::vec::Vec;
 use core::cmp::Ordering::{Equal, Greater, Less};
-use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Range};
 use num_traits::{ToPrimitive, Zero};
 
 // Negation in two's complement.
@@ -48,7 +48,7 @@ fn bitand_pos_neg(a: &mut [BigDigit], b: &[BigDigit]) {
 // - 1 & +ff = ...f ff & ...0 ff = ...0 ff = +ff
 // -ff & + 1 = ...f 01 & ...0 01 = ...0 01 = + 1
 // answer is pos, has length of b
-fn bitand_neg_pos(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitand_neg_pos(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_a = 1;
     for (ai, &bi) in a.iter_mut().zip(b.iter()) {
         let twos_a = negate_carry(*ai, &mut carry_a);
@@ -69,7 +69,7 @@ fn bitand_neg_pos(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
 // -ff & - 1 = ...f 01 & ...f ff = ...f 01 = - ff
 // -ff & -fe = ...f 01 & ...f 02 = ...f 00 = -100
 // answer is neg, has length of longest with a possible carry
-fn bitand_neg_neg(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitand_neg_neg(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_a = 1;
     let mut carry_b = 1;
     let mut carry_and = 1;
@@ -173,7 +173,7 @@ impl BitAndAssign<&Self> for BigInt {
 // + 1 | -ff = ...0 01 | ...f 01 = ...f 01 = -ff
 // +ff | - 1 = ...0 ff | ...f ff = ...f ff = - 1
 // answer is neg, has length of b
-fn bitor_pos_neg(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitor_pos_neg(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_b = 1;
     let mut carry_or = 1;
     for (ai, &bi) in a.iter_mut().zip(b.iter()) {
@@ -224,7 +224,7 @@ fn bitor_neg_pos(a: &mut [BigDigit], b: &[BigDigit]) {
 // - 1 | -ff = ...f ff | ...f 01 = ...f ff = -1
 // -ff | - 1 = ...f 01 | ...f ff = ...f ff = -1
 // answer is neg, has length of shortest
-fn bitor_neg_neg(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitor_neg_neg(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_a = 1;
     let mut carry_b = 1;
     let mut carry_or = 1;
@@ -308,7 +308,7 @@ impl BitOrAssign<&Self> for BigInt {
 // + 1 ^ -ff = ...0 01 ^ ...f 01 = ...f 00 = -100
 // +ff ^ - 1 = ...0 ff ^ ...f ff = ...f 00 = -100
 // answer is neg, has length of longest with a possible carry
-fn bitxor_pos_neg(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitxor_pos_neg(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_b = 1;
     let mut carry_xor = 1;
     for (ai, &bi) in a.iter_mut().zip(b.iter()) {
@@ -341,7 +341,7 @@ fn bitxor_pos_neg(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
 // - 1 ^ +ff = ...f ff ^ ...0 ff = ...f 00 = -100
 // -ff ^ + 1 = ...f 01 ^ ...0 01 = ...f 00 = -100
 // answer is neg, has length of longest with a possible carry
-fn bitxor_neg_pos(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitxor_neg_pos(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_a = 1;
     let mut carry_xor = 1;
     for (ai, &bi) in a.iter_mut().zip(b.iter()) {
@@ -374,7 +374,7 @@ fn bitxor_neg_pos(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
 // - 1 ^ -ff = ...f ff ^ ...f 01 = ...0 fe = +fe
 // -ff & - 1 = ...f 01 ^ ...f ff = ...0 fe = +fe
 // answer is pos, has length of longest
-fn bitxor_neg_neg(a: &mut Vec<BigDigit>, b: &[BigDigit]) {
+fn bitxor_neg_neg(a: &mut DigitVec, b: &[BigDigit]) {
     let mut carry_a = 1;
     let mut carry_b = 1;
     for (ai, &bi) in a.iter_mut().zip(b.iter()) {
@@ -449,6 +449,184 @@ impl BitXorAssign<&Self> for BigInt {
     }
 }
 
+macro_rules! impl_scalar_bitops {
+    ($($scalar:ty),*) => {$(
+        forward_all_scalar_binop_to_val_val_commutative!(impl BitAnd<$scalar> for BigInt, bitand);
+        forward_all_scalar_binop_to_val_val_commutative!(impl BitOr<$scalar> for BigInt, bitor);
+        forward_all_scalar_binop_to_val_val_commutative!(impl BitXor<$scalar> for BigInt, bitxor);
+
+        impl BitAnd<$scalar> for BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn bitand(mut self, other: $scalar) -> BigInt {
+                self &= other;
+                self
+            }
+        }
+
+        impl BitOr<$scalar> for BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn bitor(mut self, other: $scalar) -> BigInt {
+                self |= other;
+                self
+            }
+        }
+
+        impl BitXor<$scalar> for BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn bitxor(mut self, other: $scalar) -> BigInt {
+                self ^= other;
+                self
+            }
+        }
+    )*};
+}
+
+impl_scalar_bitops!(u32, u64, u128, i32, i64, i128);
+
+macro_rules! impl_unsigned_scalar_bitops_assign {
+    ($($scalar:ty),*) => {$(
+        impl BitAndAssign<$scalar> for BigInt {
+            #[allow(clippy::cast_lossless)]
+            fn bitand_assign(&mut self, other: $scalar) {
+                match self.sign {
+                    NoSign => {}
+                    Plus => {
+                        self.data &= other;
+                        if self.data.is_zero() {
+                            self.sign = NoSign;
+                        }
+                    }
+                    Minus => {
+                        let (digits, len) = big_digit::from_u128(other as u128);
+                        bitand_neg_pos(self.digits_mut(), &digits[..len]);
+                        self.sign = Plus;
+                        self.normalize();
+                    }
+                }
+            }
+        }
+
+        impl BitOrAssign<$scalar> for BigInt {
+            #[allow(clippy::cast_lossless)]
+            fn bitor_assign(&mut self, other: $scalar) {
+                match self.sign {
+                    NoSign => *self = BigInt::from(other),
+                    Plus => self.data |= other,
+                    Minus => {
+                        let (digits, len) = big_digit::from_u128(other as u128);
+                        bitor_neg_pos(self.digits_mut(), &digits[..len]);
+                        self.normalize();
+                    }
+                }
+            }
+        }
+
+        impl BitXorAssign<$scalar> for BigInt {
+            #[allow(clippy::cast_lossless)]
+            fn bitxor_assign(&mut self, other: $scalar) {
+                match self.sign {
+                    NoSign => *self = BigInt::from(other),
+                    Plus => {
+                        self.data ^= other;
+                        if self.data.is_zero() {
+                            self.sign = NoSign;
+                        }
+                    }
+                    Minus => {
+                        let (digits, len) = big_digit::from_u128(other as u128);
+                        bitxor_neg_pos(self.digits_mut(), &digits[..len]);
+                        self.normalize();
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_unsigned_scalar_bitops_assign!(u32, u64, u128);
+
+macro_rules! impl_signed_scalar_bitops_assign {
+    ($($scalar:ty),*) => {$(
+        impl BitAndAssign<$scalar> for BigInt {
+            #[allow(clippy::cast_lossless)]
+            fn bitand_assign(&mut self, other: $scalar) {
+                if other >= 0 {
+                    *self &= other as u128;
+                    return;
+                }
+                let (digits, len) = big_digit::from_u128(other.unsigned_abs() as u128);
+                match self.sign {
+                    NoSign => {}
+                    Plus => bitand_pos_neg(self.digits_mut(), &digits[..len]),
+                    Minus => bitand_neg_neg(self.digits_mut(), &digits[..len]),
+                }
+                self.normalize();
+            }
+        }
+
+        impl BitOrAssign<$scalar> for BigInt {
+            #[allow(clippy::cast_lossless)]
+            fn bitor_assign(&mut self, other: $scalar) {
+                if other >= 0 {
+                    *self |= other as u128;
+                    return;
+                }
+                let (digits, len) = big_digit::from_u128(other.unsigned_abs() as u128);
+                match self.sign {
+                    NoSign => *self = BigInt::from(other),
+                    Plus => {
+                        bitor_pos_neg(self.digits_mut(), &digits[..len]);
+                        self.sign = Minus;
+                        self.normalize();
+                    }
+                    Minus => {
+                        bitor_neg_neg(self.digits_mut(), &digits[..len]);
+                        self.normalize();
+                    }
+                }
+            }
+        }
+
+        impl BitXorAssign<$scalar> for BigInt {
+            #[allow(clippy::cast_lossless)]
+            fn bitxor_assign(&mut self, other: $scalar) {
+                if other >= 0 {
+                    *self ^= other as u128;
+                    return;
+                }
+                let (digits, len) = big_digit::from_u128(other.unsigned_abs() as u128);
+                match self.sign {
+                    NoSign => *self = BigInt::from(other),
+                    Plus => {
+                        bitxor_pos_neg(self.digits_mut(), &digits[..len]);
+                        self.sign = Minus;
+                        self.normalize();
+                    }
+                    Minus => {
+                        bitxor_neg_neg(self.digits_mut(), &digits[..len]);
+                        self.sign = Plus;
+                        self.normalize();
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_signed_scalar_bitops_assign!(i32, i64, i128);
+promote_all_scalars!(impl BitAnd for BigInt, bitand);
+promote_all_scalars_assign!(impl BitAndAssign for BigInt, bitand_assign);
+promote_all_scalars!(impl BitOr for BigInt, bitor);
+promote_all_scalars_assign!(impl BitOrAssign for BigInt, bitor_assign);
+promote_all_scalars!(impl BitXor for BigInt, bitxor);
+promote_all_scalars_assign!(impl BitXorAssign for BigInt, bitxor_assign);
+
 pub(super) fn set_negative_bit(x: &mut BigInt, bit: u64, value: bool) {
     debug_assert_eq!(x.sign, Minus);
     let data = &mut x.data;
@@ -529,3 +707,46 @@ pub(super) fn set_negative_bit(x: &mut BigInt, bit: u64, value: bool) {
         }
     }
 }
+
+pub(super) fn extract_bits(x: &BigInt, range: Range<u64>) -> BigUint {
+    if x.sign != Minus {
+        return x.data.extract_bits(range);
+    }
+    if range.start >= range.end {
+        return BigUint::ZERO;
+    }
+    // The two's complement of a negative `x` is the complement of `|x| - 1`, so we extract that
+    // field of the magnitude, and only borrow from it if no lower bits are set.
+    let mut field = x.data.extract_bits(range.clone());
+    if x.data.trailing_zeros() >= Some(range.start) {
+        if field.is_zero() {
+            // The borrow passes through, leaving all ones to complement.
+            return BigUint::ZERO;
+        }
+        field -= 1u32;
+    }
+    field.not_bits(range.end - range.start)
+}
+
+/// Modifies the bit range of the two's complement of `x`, applying `pos` to the magnitude of a
+/// non-negative `x`, or `neg` to `|x| - 1` of a negative `x`, whose bits are the complement of
+/// the two's complement. Either way, bits past the end of the data only cost anything if the
+/// operation sets them.
+pub(super) fn update_bits(
+    x: &mut BigInt,
+    range: Range<u64>,
+    pos: impl FnOnce(&mut BigUint),
+    neg: impl FnOnce(&mut BigUint),
+) {
+    if range.start >= range.end {
+        return;
+    }
+    if x.sign == Minus {
+        x.data -= 1u32;
+        neg(&mut x.data);
+        x.data += 1u32;
+    } else {
+        pos(&mut x.data);
+        x.sign = if x.data.is_zero() { NoSign } else { Plus };
+    }
+}
@@ -24,17 +24,16 @@ impl Num for BigInt {
 
     /// Creates and initializes a [`BigInt`].
     #[inline]
-    fn from_str_radix(mut s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
-        let sign = if let Some(tail) = s.strip_prefix('-') {
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        if let Some(tail) = s.strip_prefix('-') {
             if !tail.starts_with('+') {
-                s = tail
+                let bu =
+                    BigUint::from_str_radix(tail, radix).map_err(ParseBigIntError::after_sign)?;
+                return Ok(Self::from_biguint(Minus, bu));
             }
-            Minus
-        } else {
-            Plus
-        };
+        }
         let bu = BigUint::from_str_radix(s, radix)?;
-        Ok(Self::from_biguint(sign, bu))
+        Ok(Self::from_biguint(Plus, bu))
     }
 }
 
@@ -440,6 +439,36 @@ pub(super) fn to_signed_bytes_le(x: &BigInt) -> Vec<u8> {
     bytes
 }
 
+/// Writes the little-endian two's complement to fill the buffer exactly, sign-extending as
+/// needed, or fails if it doesn't fit.
+pub(super) fn write_signed_bytes_le(
+    x: &BigInt,
+    buf: &mut [u8],
+) -> Result<(), TryFromBigIntError<()>> {
+    // The number of bits including the sign bit, where only a negative power of two doesn't need
+    // one more than its magnitude.
+    let bits = match x.sign {
+        NoSign => 0,
+        Plus => x.data.bits() + 1,
+        Minus => {
+            let bits = x.data.bits();
+            if x.data.trailing_zeros() == Some(bits - 1) {
+                bits
+            } else {
+                bits + 1
+            }
+        }
+    };
+    if bits > buf.len() as u64 * 8 {
+        return Err(TryFromBigIntError::new(()));
+    }
+    x.data.write_bytes_le(buf)?;
+    if x.sign == Minus {
+        twos_complement_le(buf);
+    }
+    Ok(())
+}
+
 /// Perform in-place two's complement of the given binary representation,
 /// in little-endian byte order.
 #[inline]
@@ -1,23 +1,25 @@
 use super::CheckedUnsignedAbs::{Negative, Positive};
-use super::Sign::{self, Minus, NoSign, Plus};
+use super::Sign::{self, NoSign};
 use super::{BigInt, UnsignedAbs};
 
+use crate::big_digit::BigDigit;
+use crate::biguint;
 use crate::{IsizePromotion, UsizePromotion};
 
 use core::iter::Product;
 use core::ops::{Mul, MulAssign};
 use num_traits::{CheckedMul, One, Zero};
 
-impl Mul<Self> for Sign {
-    type Output = Self;
-
-    #[inline]
-    fn mul(self, other: Self) -> Self {
-        match (self, other) {
-            (NoSign, _) | (_, NoSign) => NoSign,
-            (Plus, Plus) | (Minus, Minus) => Plus,
-            (Plus, Minus) | (Minus, Plus) => Minus,
-        }
+/// Computes `acc += sign * b * c` in place, where `b` and `c` are magnitudes.
+pub(super) fn add_mul(acc: &mut BigInt, sign: Sign, b: &[BigDigit], c: &[BigDigit]) {
+    if b.is_empty() || c.is_empty() {
+        return;
+    }
+    if acc.sign == NoSign || acc.sign == sign {
+        biguint::add_mul(&mut acc.data, b, c);
+        acc.sign = sign;
+    } else {
+        acc.sign = acc.sign * biguint::sub_mul(&mut acc.data, b, c);
     }
 }
 
@@ -2,8 +2,10 @@
 #![cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 
 use super::{BigInt, Sign};
+use crate::{BigUint, MaxBits};
 
-use serde::de::{Error, Unexpected};
+use core::fmt;
+use serde::de::{self, DeserializeSeed, Error, SeqAccess, Unexpected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 impl Serialize for Sign {
@@ -59,3 +61,42 @@ impl<'de> Deserialize<'de> for BigInt {
         Ok(BigInt::from_biguint(sign, data))
     }
 }
+
+impl<'de> DeserializeSeed<'de> for MaxBits<BigInt> {
+    type Value = BigInt;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, BoundedVisitor(self.max_bits()))
+    }
+}
+
+/// Visits the `(sign, magnitude)` tuple, bounding the magnitude like `MaxBits<BigUint>`.
+struct BoundedVisitor(u64);
+
+impl<'de> Visitor<'de> for BoundedVisitor {
+    type Value = BigInt;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "a sign and a magnitude of at most {} bits",
+            self.0
+        )
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let sign: Sign = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let data = seq
+            .next_element_seed(MaxBits::<BigUint>::new(self.0))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(BigInt::from_biguint(sign, data))
+    }
+}
@@ -16,14 +16,13 @@ macro_rules! bigint_sub {
     ($a:expr, $a_owned:expr, $a_data:expr, $b:expr, $b_owned:expr, $b_data:expr) => {
         match ($a.sign, $b.sign) {
             (_, NoSign) => $a_owned,
-            (NoSign, _) => -$b_owned,
+            (NoSign, _) => BigInt::from_biguint(-$b.sign, $a_data + $b_data),
             // opposite signs => keep the sign of the left with the sum of magnitudes
             (Plus, Minus) | (Minus, Plus) => BigInt::from_biguint($a.sign, $a_data + $b_data),
             // same sign => keep or toggle the sign of the left with the difference of magnitudes
             (Plus, Plus) | (Minus, Minus) => match $a.data.cmp(&$b.data) {
                 Less => BigInt::from_biguint(-$a.sign, $b_data - $a_data),
-                Greater => BigInt::from_biguint($a.sign, $a_data - $b_data),
-                Equal => BigInt::ZERO,
+                Greater | Equal => BigInt::from_biguint($a.sign, $a_data - $b_data),
             },
         }
     };
@@ -93,11 +92,9 @@ impl Sub<u32> for BigInt {
     #[inline]
     fn sub(self, other: u32) -> Self {
         match self.sign {
-            NoSign => -Self::from(other),
-            Minus => -Self::from(self.data + other),
+            NoSign | Minus => -Self::from(self.data + other),
             Plus => match self.data.cmp(&From::from(other)) {
-                Equal => Self::ZERO,
-                Greater => Self::from(self.data - other),
+                Greater | Equal => Self::from(self.data - other),
                 Less => -Self::from(other - self.data),
             },
         }
@@ -144,11 +141,9 @@ impl Sub<u64> for BigInt {
     #[inline]
     fn sub(self, other: u64) -> Self {
         match self.sign {
-            NoSign => -Self::from(other),
-            Minus => -Self::from(self.data + other),
+            NoSign | Minus => -Self::from(self.data + other),
             Plus => match self.data.cmp(&From::from(other)) {
-                Equal => Self::ZERO,
-                Greater => Self::from(self.data - other),
+                Greater | Equal => Self::from(self.data - other),
                 Less => -Self::from(other - self.data),
             },
         }
@@ -169,11 +164,9 @@ impl Sub<u128> for BigInt {
     #[inline]
     fn sub(self, other: u128) -> Self {
         match self.sign {
-            NoSign => -Self::from(other),
-            Minus => -Self::from(self.data + other),
+            NoSign | Minus => -Self::from(self.data + other),
             Plus => match self.data.cmp(&From::from(other)) {
-                Equal => Self::ZERO,
-                Greater => Self::from(self.data - other),
+                Greater | Equal => Self::from(self.data - other),
                 Less => -Self::from(other - self.data),
             },
         }
@@ -0,0 +1,297 @@
+//! Borrowed, read-only views of signed big integers stored in external digit slices.
+
+use super::BigInt;
+use super::Sign::{self, Minus, NoSign, Plus};
+
+use crate::format;
+use crate::BigUintRef;
+
+use core::cmp::Ordering::{self, Equal, Greater, Less};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Rem, Sub};
+use num_traits::ToPrimitive;
+
+/// A borrowed view of a signed big integer, made of a [`Sign`] and a [`BigUintRef`] magnitude.
+///
+/// Like [`BigUintRef`], creating a view never copies the digits. Comparisons, `bits` and
+/// primitive conversions work directly on the borrowed digits, while formatting and arithmetic
+/// produce owned results.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{BigInt, BigIntRef, Sign};
+///
+/// let column: &[u32] = &[5, 0];
+/// let view = BigIntRef::from_u32_slice(Sign::Minus, column);
+/// assert_eq!(view, BigInt::from(-5));
+/// assert_eq!(&view * &BigInt::from(-3), BigInt::from(15));
+/// assert_eq!(format!("{:x}", view), "-5");
+/// ```
+#[derive(Clone, Copy)]
+pub struct BigIntRef<'a> {
+    sign: Sign,
+    magnitude: BigUintRef<'a>,
+}
+
+impl<'a> BigIntRef<'a> {
+    /// Creates a view from a sign and a magnitude, like [`BigInt::from_biguint`].
+    ///
+    /// The sign is ignored if the magnitude is zero, and vice versa.
+    #[inline]
+    pub fn new(sign: Sign, magnitude: BigUintRef<'a>) -> Self {
+        if sign == NoSign || magnitude.is_zero() {
+            BigIntRef {
+                sign: NoSign,
+                magnitude: BigUintRef::from_u32_slice(&[]),
+            }
+        } else {
+            BigIntRef { sign, magnitude }
+        }
+    }
+
+    /// Creates a view of base 2<sup>32</sup> digits, ordered least significant digit first.
+    #[inline]
+    pub fn from_u32_slice(sign: Sign, digits: &'a [u32]) -> Self {
+        Self::new(sign, BigUintRef::from_u32_slice(digits))
+    }
+
+    /// Creates a view of base 2<sup>64</sup> digits, ordered least significant digit first.
+    #[inline]
+    pub fn from_u64_slice(sign: Sign, digits: &'a [u64]) -> Self {
+        Self::new(sign, BigUintRef::from_u64_slice(digits))
+    }
+
+    /// Returns the sign of the viewed value.
+    #[inline]
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    /// Returns a view of the magnitude.
+    #[inline]
+    pub fn magnitude(&self) -> BigUintRef<'a> {
+        self.magnitude
+    }
+
+    /// Determines the fewest bits necessary to express the magnitude of the viewed value.
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        self.magnitude.bits()
+    }
+
+    /// Copies the viewed value into an owned [`BigInt`].
+    pub fn to_bigint(&self) -> BigInt {
+        BigInt::from_biguint(self.sign, self.magnitude.to_biguint())
+    }
+}
+
+impl<'a> From<&'a BigInt> for BigIntRef<'a> {
+    #[inline]
+    fn from(n: &'a BigInt) -> Self {
+        BigIntRef {
+            sign: n.sign,
+            magnitude: BigUintRef::from(&n.data),
+        }
+    }
+}
+
+impl From<BigIntRef<'_>> for BigInt {
+    #[inline]
+    fn from(view: BigIntRef<'_>) -> Self {
+        view.to_bigint()
+    }
+}
+
+impl PartialEq for BigIntRef<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Equal
+    }
+}
+
+impl Eq for BigIntRef<'_> {}
+
+impl PartialOrd for BigIntRef<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigIntRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scmp = self.sign.cmp(&other.sign);
+        if scmp != Equal {
+            return scmp;
+        }
+
+        match self.sign {
+            NoSign => Equal,
+            Plus => self.magnitude.cmp(&other.magnitude),
+            Minus => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl PartialEq<BigInt> for BigIntRef<'_> {
+    #[inline]
+    fn eq(&self, other: &BigInt) -> bool {
+        *self == BigIntRef::from(other)
+    }
+}
+
+impl PartialEq<BigIntRef<'_>> for BigInt {
+    #[inline]
+    fn eq(&self, other: &BigIntRef<'_>) -> bool {
+        BigIntRef::from(self) == *other
+    }
+}
+
+impl PartialOrd<BigInt> for BigIntRef<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(&BigIntRef::from(other)))
+    }
+}
+
+impl PartialOrd<BigIntRef<'_>> for BigInt {
+    #[inline]
+    fn partial_cmp(&self, other: &BigIntRef<'_>) -> Option<Ordering> {
+        Some(BigIntRef::from(self).cmp(other))
+    }
+}
+
+macro_rules! impl_view_fmt {
+    ($($Trait:ident: $prefix:expr, $radix:expr, $upper:expr;)*) => {$(
+        impl fmt::$Trait for BigIntRef<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let magnitude = self.magnitude.to_biguint();
+                format::fmt_radix(f, self.sign != Minus, $prefix, &magnitude, $radix, $upper)
+            }
+        }
+    )*};
+}
+
+impl_view_fmt! {
+    Debug: "", 10, false;
+    Display: "", 10, false;
+    LowerHex: "0x", 16, false;
+    UpperHex: "0x", 16, true;
+    Binary: "0b", 2, false;
+    Octal: "0o", 8, false;
+}
+
+impl ToPrimitive for BigIntRef<'_> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.to_i128().as_ref().and_then(i128::to_i64)
+    }
+
+    #[inline]
+    fn to_i128(&self) -> Option<i128> {
+        let n = self.magnitude.to_u128()?;
+        match self.sign {
+            Plus | NoSign => n.to_i128(),
+            Minus if n <= 1 << 127 => Some((n as i128).wrapping_neg()),
+            Minus => None,
+        }
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u128().as_ref().and_then(u128::to_u64)
+    }
+
+    #[inline]
+    fn to_u128(&self) -> Option<u128> {
+        match self.sign {
+            Plus | NoSign => self.magnitude.to_u128(),
+            Minus => None,
+        }
+    }
+
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        let n = self.magnitude.to_f32()?;
+        Some(if self.sign == Minus { -n } else { n })
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        let n = self.magnitude.to_f64()?;
+        Some(if self.sign == Minus { -n } else { n })
+    }
+}
+
+fn add(a: BigIntRef<'_>, b: BigIntRef<'_>) -> BigInt {
+    match (a.sign, b.sign) {
+        (_, NoSign) => a.to_bigint(),
+        (NoSign, _) => b.to_bigint(),
+        (a_sign, b_sign) if a_sign == b_sign => {
+            BigInt::from_biguint(a_sign, &a.magnitude + &b.magnitude)
+        }
+        (a_sign, b_sign) => match a.magnitude.cmp(&b.magnitude) {
+            Less => BigInt::from_biguint(b_sign, &b.magnitude - &a.magnitude),
+            Greater => BigInt::from_biguint(a_sign, &a.magnitude - &b.magnitude),
+            Equal => BigInt::ZERO,
+        },
+    }
+}
+
+fn sub(a: BigIntRef<'_>, b: BigIntRef<'_>) -> BigInt {
+    add(a, BigIntRef { sign: -b.sign, ..b })
+}
+
+fn mul(a: BigIntRef<'_>, b: BigIntRef<'_>) -> BigInt {
+    BigInt::from_biguint(a.sign * b.sign, &a.magnitude * &b.magnitude)
+}
+
+fn div(a: BigIntRef<'_>, b: BigIntRef<'_>) -> BigInt {
+    a.to_bigint() / b.to_bigint()
+}
+
+fn rem(a: BigIntRef<'_>, b: BigIntRef<'_>) -> BigInt {
+    a.to_bigint() % b.to_bigint()
+}
+
+// Addition, subtraction and multiplication read both operands in place, while division works
+// on owned copies.
+macro_rules! impl_view_binop {
+    ($(impl $Op:ident, $op:ident => $kernel:ident;)*) => {$(
+        impl $Op<&BigIntRef<'_>> for &BigIntRef<'_> {
+            type Output = BigInt;
+
+            #[inline]
+            fn $op(self, other: &BigIntRef<'_>) -> BigInt {
+                $kernel(*self, *other)
+            }
+        }
+
+        impl $Op<&BigInt> for &BigIntRef<'_> {
+            type Output = BigInt;
+
+            #[inline]
+            fn $op(self, other: &BigInt) -> BigInt {
+                $kernel(*self, other.into())
+            }
+        }
+
+        impl $Op<&BigIntRef<'_>> for &BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn $op(self, other: &BigIntRef<'_>) -> BigInt {
+                $kernel(self.into(), *other)
+            }
+        }
+    )*};
+}
+
+impl_view_binop! {
+    impl Add, add => add;
+    impl Sub, sub => sub;
+    impl Mul, mul => mul;
+    impl Div, div => div;
+    impl Rem, rem => rem;
+}
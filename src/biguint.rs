@@ -1,4 +1,10 @@
 use crate::big_digit::{self, BigDigit};
+use crate::encoding::{der, leb128, pgp, ssh};
+use crate::format;
+use crate::{
+    AllocBigIntError, BigFormat, BigInt, DecodeBigIntError, DecodeErrorKind, LiteralOptions,
+    ParseBigIntError, Sign, TryFromBigIntError,
+};
 
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -8,6 +14,7 @@ use core::default::Default;
 use core::fmt;
 use core::hash;
 use core::mem;
+use core::ops::Range;
 use core::str;
 
 use num_integer::{Integer, Roots};
@@ -23,16 +30,25 @@ mod bits;
 mod convert;
 mod iter;
 mod monty;
+mod parser;
 mod power;
 mod serde;
 mod shift;
-
-pub(crate) use self::convert::to_str_radix_reversed;
-pub use self::iter::{U32Digits, U64Digits};
+mod storage;
+mod view;
+
+pub(crate) use self::convert::{to_str_radix_reversed, RadixDigits};
+pub use self::iter::{Ones, U32Digits, U64Digits, Zeros};
+pub(crate) use self::multiplication::{add_mul, sub_mul};
+pub use self::parser::BigUintParser;
+#[cfg(feature = "serde")]
+pub use self::serde::MaxBits;
+pub(crate) use self::storage::DigitVec;
+pub use self::view::BigUintRef;
 
 /// A big unsigned integer type.
 pub struct BigUint {
-    data: Vec<BigDigit>,
+    data: DigitVec,
 }
 
 // Note: derived `Clone` doesn't specialize `clone_from`,
@@ -109,33 +125,31 @@ impl fmt::Debug for BigUint {
 
 impl fmt::Display for BigUint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(true, "", &self.to_str_radix(10))
+        format::fmt_radix(f, true, "", self, 10, false)
     }
 }
 
 impl fmt::LowerHex for BigUint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(true, "0x", &self.to_str_radix(16))
+        format::fmt_radix(f, true, "0x", self, 16, false)
     }
 }
 
 impl fmt::UpperHex for BigUint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut s = self.to_str_radix(16);
-        s.make_ascii_uppercase();
-        f.pad_integral(true, "0x", &s)
+        format::fmt_radix(f, true, "0x", self, 16, true)
     }
 }
 
 impl fmt::Binary for BigUint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(true, "0b", &self.to_str_radix(2))
+        format::fmt_radix(f, true, "0b", self, 2, false)
     }
 }
 
 impl fmt::Octal for BigUint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.pad_integral(true, "0o", &self.to_str_radix(8))
+        format::fmt_radix(f, true, "0o", self, 8, false)
     }
 }
 
@@ -164,7 +178,9 @@ impl ConstZero for BigUint {
 impl One for BigUint {
     #[inline]
     fn one() -> Self {
-        Self { data: vec![1] }
+        Self {
+            data: DigitVec::from_slice(&[1]),
+        }
     }
 
     #[inline]
@@ -519,17 +535,63 @@ pub trait ToBigUint {
     fn to_biguint(&self) -> Option<BigUint>;
 }
 
+/// Returns the number of digits needed to hold `bits` bits, saturating like `set_bit` as any
+/// such case is guaranteed to fail allocation.
+fn digits_for_bits(bits: u64) -> usize {
+    Integer::div_ceil(&bits, &u64::from(big_digit::BITS))
+        .to_usize()
+        .unwrap_or(usize::MAX)
+}
+
 /// Creates and initializes a [`BigUint`].
 ///
 /// The digits are in little-endian base matching `BigDigit`.
 #[inline]
-pub(crate) fn biguint_from_vec(digits: Vec<BigDigit>) -> BigUint {
-    BigUint { data: digits }.normalized()
+pub(crate) fn biguint_from_vec(digits: impl Into<DigitVec>) -> BigUint {
+    BigUint {
+        data: digits.into(),
+    }
+    .normalized()
 }
 
 impl BigUint {
     /// A constant `BigUint` with value 0, useful for static initialization.
-    pub const ZERO: Self = Self { data: Vec::new() };
+    pub const ZERO: Self = Self {
+        data: DigitVec::new(),
+    };
+
+    /// Creates a [`BigUint`] from a `u64` in a const context, for constants and statics.
+    ///
+    /// For values too large for a primitive, see the [`biguint!`](crate::biguint!) macro.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// static MERSENNE_61: BigUint = BigUint::from_u64_const((1 << 61) - 1);
+    /// assert_eq!(MERSENNE_61, BigUint::from(u64::MAX >> 3));
+    /// ```
+    #[inline]
+    pub const fn from_u64_const(n: u64) -> Self {
+        Self::from_u128_const(n as u128)
+    }
+
+    /// Creates a [`BigUint`] from a `u128` in a const context, for constants and statics.
+    #[inline]
+    pub const fn from_u128_const(n: u128) -> Self {
+        let (digits, len) = big_digit::from_u128(n);
+        BigUint {
+            data: DigitVec::from_inline(digits, len),
+        }
+    }
+
+    /// Creates a [`BigUint`] from static digits without trailing zeros, in a const context.
+    pub(crate) const fn from_static_digits(digits: &'static [BigDigit]) -> Self {
+        BigUint {
+            data: DigitVec::from_static(digits),
+        }
+    }
 
     /// Creates and initializes a [`BigUint`].
     ///
@@ -540,7 +602,7 @@ impl BigUint {
 
         cfg_digit_expr!(
             {
-                big.data = digits;
+                big.data = digits.into();
                 big.normalize();
             },
             big.assign_from_slice(&digits)
@@ -637,6 +699,59 @@ impl BigUint {
         Self::from_str_radix(s, radix).ok()
     }
 
+    /// Parses a string in the given `radix` like [`Num::from_str_radix`], but fails with
+    /// [`BigIntErrorKind::TooLong`][crate::BigIntErrorKind::TooLong] if the value would have
+    /// more than `max_bits` bits.
+    ///
+    /// Inputs with too many significant digits are rejected before any conversion work, so
+    /// this is suitable for untrusted input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigIntErrorKind, BigUint};
+    ///
+    /// assert_eq!(BigUint::from_str_radix_max_bits("255", 10, 8), Ok(BigUint::from(255u32)));
+    /// let err = BigUint::from_str_radix_max_bits("256", 10, 8).unwrap_err();
+    /// assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+    /// ```
+    pub fn from_str_radix_max_bits(
+        s: &str,
+        radix: u32,
+        max_bits: u64,
+    ) -> Result<Self, ParseBigIntError> {
+        convert::from_str_radix(s, radix, max_bits)
+    }
+
+    /// Parses an integer written like a Rust literal, with an optional `+` sign, an optional
+    /// `0b`, `0o`, or `0x` radix prefix, and `_` separators between digits.
+    ///
+    /// This is the same as [`parse_literal_with`][Self::parse_literal_with] using the default
+    /// [`LiteralOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::parse_literal("0x_dead_beef"), Ok(BigUint::from(0xdead_beef_u32)));
+    /// assert_eq!(BigUint::parse_literal("0o777"), Ok(BigUint::from(0o777u32)));
+    /// assert_eq!(BigUint::parse_literal("1_000_000"), Ok(BigUint::from(1_000_000u32)));
+    /// assert!(BigUint::parse_literal("-1").is_err());
+    /// ```
+    pub fn parse_literal(s: &str) -> Result<Self, ParseBigIntError> {
+        Self::parse_literal_with(s, &LiteralOptions::new())
+    }
+
+    /// Parses an integer written like a Rust literal, as configured by `options`.
+    /// A `-` sign is rejected as an invalid digit.
+    pub fn parse_literal_with(s: &str, options: &LiteralOptions) -> Result<Self, ParseBigIntError> {
+        match options.parse(s)? {
+            (true, _) => Err(ParseBigIntError::invalid(0)),
+            (false, n) => Ok(n),
+        }
+    }
+
     /// Creates and initializes a [`BigUint`]. Each `u8` of the input slice is
     /// interpreted as one digit of the number
     /// and must therefore be less than `radix`.
@@ -713,6 +828,244 @@ impl BigUint {
         }
     }
 
+    /// Returns the ASN.1 DER encoding of the [`BigUint`] as an `INTEGER`, including the tag and
+    /// length octets. A leading zero octet is added when the high bit would otherwise be set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(128u32).to_der_integer(), vec![0x02, 0x02, 0x00, 0x80]);
+    /// ```
+    pub fn to_der_integer(&self) -> Vec<u8> {
+        let mut content = self.to_bytes_be();
+        if content[0] >= 0x80 {
+            content.insert(0, 0);
+        }
+        der::encode(&content)
+    }
+
+    /// Decodes a [`BigUint`] from exactly one ASN.1 DER `INTEGER`, including the tag and length
+    /// octets.
+    ///
+    /// Encodings that are valid BER but not minimal DER are rejected, as are negative values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigUint, DecodeErrorKind};
+    ///
+    /// let n = BigUint::from_der_integer(&[0x02, 0x02, 0x00, 0x80]).unwrap();
+    /// assert_eq!(n, BigUint::from(128u32));
+    ///
+    /// let err = BigUint::from_der_integer(&[0x02, 0x01, 0x80]).unwrap_err();
+    /// assert_eq!(err.kind(), DecodeErrorKind::Negative);
+    /// ```
+    pub fn from_der_integer(bytes: &[u8]) -> Result<Self, DecodeBigIntError> {
+        let content = der::decode(bytes)?;
+        if content[0] >= 0x80 {
+            return Err(DecodeBigIntError::new(DecodeErrorKind::Negative));
+        }
+        Ok(Self::from_bytes_be(content))
+    }
+
+    /// Returns the SSH `mpint` encoding of the [`BigUint`]: a big-endian 32-bit length, then the
+    /// minimal two's-complement bytes, with zero having no bytes at all. A leading zero byte is
+    /// added when the high bit would otherwise be set.
+    ///
+    /// Panics if the encoding would be longer than `u32::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(0x80u32).to_ssh_mpint(), vec![0, 0, 0, 2, 0x00, 0x80]);
+    /// ```
+    pub fn to_ssh_mpint(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return ssh::encode(&[]);
+        }
+        let mut content = self.to_bytes_be();
+        if content[0] >= 0x80 {
+            content.insert(0, 0);
+        }
+        ssh::encode(&content)
+    }
+
+    /// Decodes a [`BigUint`] from an SSH `mpint` at the start of `bytes`, returning it along with
+    /// the rest of the input.
+    ///
+    /// Negative values are rejected, as are encodings with unnecessary leading zero bytes. Use
+    /// [`from_ssh_mpint_lenient`][Self::from_ssh_mpint_lenient] to accept the latter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigUint, DecodeErrorKind};
+    ///
+    /// let (n, rest) = BigUint::from_ssh_mpint(&[0, 0, 0, 2, 0x00, 0x80]).unwrap();
+    /// assert_eq!(n, BigUint::from(0x80u32));
+    /// assert!(rest.is_empty());
+    ///
+    /// let err = BigUint::from_ssh_mpint(&[0, 0, 0, 1, 0x80]).unwrap_err();
+    /// assert_eq!(err.kind(), DecodeErrorKind::Negative);
+    /// ```
+    pub fn from_ssh_mpint(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        let (content, rest) = ssh::decode(bytes, true)?;
+        Ok((Self::from_ssh_content(content)?, rest))
+    }
+
+    /// Decodes a [`BigUint`] from an SSH `mpint` at the start of `bytes`, like
+    /// [`from_ssh_mpint`][Self::from_ssh_mpint], but accepting encodings that are not minimal.
+    pub fn from_ssh_mpint_lenient(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        let (content, rest) = ssh::decode(bytes, false)?;
+        Ok((Self::from_ssh_content(content)?, rest))
+    }
+
+    fn from_ssh_content(content: &[u8]) -> Result<Self, DecodeBigIntError> {
+        match content.first() {
+            Some(&b) if b >= 0x80 => Err(DecodeBigIntError::new(DecodeErrorKind::Negative)),
+            _ => Ok(Self::from_bytes_be(content)),
+        }
+    }
+
+    /// Returns the OpenPGP MPI encoding of the [`BigUint`]: a big-endian 16-bit count of the
+    /// significant bits, then the magnitude bytes.
+    ///
+    /// Returns `None` if the number has more than 65535 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(0x1ffu32).to_pgp_mpi(), Some(vec![0, 9, 0x01, 0xff]));
+    /// ```
+    pub fn to_pgp_mpi(&self) -> Option<Vec<u8>> {
+        pgp::encode(self)
+    }
+
+    /// Decodes a [`BigUint`] from an OpenPGP MPI at the start of `bytes`, returning it along with
+    /// the rest of the input.
+    ///
+    /// The bit count must be exact. Use [`from_pgp_mpi_lenient`][Self::from_pgp_mpi_lenient] to
+    /// accept encodings with leading zero bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigUint, DecodeErrorKind};
+    ///
+    /// let (n, rest) = BigUint::from_pgp_mpi(&[0, 9, 0x01, 0xff, 0xaa]).unwrap();
+    /// assert_eq!(n, BigUint::from(0x1ffu32));
+    /// assert_eq!(rest, &[0xaa]);
+    ///
+    /// let err = BigUint::from_pgp_mpi(&[0, 10, 0x01, 0xff]).unwrap_err();
+    /// assert_eq!(err.kind(), DecodeErrorKind::NonMinimal);
+    /// ```
+    pub fn from_pgp_mpi(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        pgp::decode(bytes, true)
+    }
+
+    /// Decodes a [`BigUint`] from an OpenPGP MPI at the start of `bytes`, like
+    /// [`from_pgp_mpi`][Self::from_pgp_mpi], but only using the bit count to find the length of
+    /// the magnitude.
+    pub fn from_pgp_mpi_lenient(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        pgp::decode(bytes, false)
+    }
+
+    /// Appends the unsigned LEB128 encoding of the [`BigUint`] to `out`, seven bits per byte
+    /// starting from the least significant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut out = Vec::new();
+    /// BigUint::from(624485u32).write_uleb128(&mut out);
+    /// assert_eq!(out, [0xe5, 0x8e, 0x26]);
+    /// ```
+    pub fn write_uleb128(&self, out: &mut Vec<u8>) {
+        leb128::write_unsigned(self, out);
+    }
+
+    /// Decodes a [`BigUint`] from unsigned LEB128 at the start of `bytes`, returning it along
+    /// with the rest of the input. Redundant high groups of zeros are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let (n, rest) = BigUint::read_uleb128(&[0xe5, 0x8e, 0x26, 0xff]).unwrap();
+    /// assert_eq!(n, BigUint::from(624485u32));
+    /// assert_eq!(rest, &[0xff]);
+    /// ```
+    pub fn read_uleb128(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        leb128::read_unsigned(bytes)
+    }
+
+    /// Writes the unsigned LEB128 encoding of the [`BigUint`] to an [`io::Write`][std::io::Write].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_uleb128_io<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let mut out = Vec::new();
+        leb128::write_unsigned(self, &mut out);
+        writer.write_all(&out)
+    }
+
+    /// Reads a [`BigUint`] encoded in unsigned LEB128 from an [`io::Read`][std::io::Read].
+    ///
+    /// This reads one byte at a time, so that nothing after the encoded value is consumed. A
+    /// buffered reader is recommended.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_uleb128_io<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
+        leb128::read_unsigned_io(reader)
+    }
+
+    /// Writes the big-endian byte representation of the [`BigUint`] to fill `buf` exactly,
+    /// with leading zeros as needed.
+    ///
+    /// Fails without writing anything if the value needs more bytes than `buf` has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut buf = [0xff; 4];
+    /// BigUint::from(1125u32).write_bytes_be(&mut buf).unwrap();
+    /// assert_eq!(buf, [0, 0, 4, 101]);
+    /// assert!(BigUint::from(1125u32).write_bytes_be(&mut buf[..1]).is_err());
+    /// ```
+    pub fn write_bytes_be(&self, buf: &mut [u8]) -> Result<(), TryFromBigIntError<()>> {
+        convert::write_bytes_le(self, buf)?;
+        buf.reverse();
+        Ok(())
+    }
+
+    /// Writes the little-endian byte representation of the [`BigUint`] to fill `buf` exactly,
+    /// with trailing zeros as needed.
+    ///
+    /// Fails without writing anything if the value needs more bytes than `buf` has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut buf = [0xff; 4];
+    /// BigUint::from(1125u32).write_bytes_le(&mut buf).unwrap();
+    /// assert_eq!(buf, [101, 4, 0, 0]);
+    /// ```
+    pub fn write_bytes_le(&self, buf: &mut [u8]) -> Result<(), TryFromBigIntError<()>> {
+        convert::write_bytes_le(self, buf)
+    }
+
     /// Returns the `u32` digits representation of the [`BigUint`] ordered least significant digit
     /// first.
     ///
@@ -805,6 +1158,56 @@ impl BigUint {
         unsafe { String::from_utf8_unchecked(v) }
     }
 
+    /// Returns a builder to format the integer with custom options, like digit grouping and
+    /// zero padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let i = BigUint::from(0xfedcba98u32);
+    /// assert_eq!(i.format().radix(2).group(8, ' ').to_string(),
+    ///            "11111110 11011100 10111010 10011000");
+    /// ```
+    #[inline]
+    pub fn format(&self) -> BigFormat<'_> {
+        BigFormat::new(Sign::Plus, self)
+    }
+
+    /// Writes the integer formatted in the given radix, like [`to_str_radix`][Self::to_str_radix],
+    /// but without building the whole string in memory first.
+    /// `radix` must be in the range `2...36`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let i = BigUint::parse_bytes(b"ff", 16).unwrap();
+    /// let mut s = String::new();
+    /// i.write_radix_to(&mut s, 2).unwrap();
+    /// assert_eq!(s, "11111111");
+    /// ```
+    pub fn write_radix_to<W: fmt::Write + ?Sized>(&self, w: &mut W, radix: u32) -> fmt::Result {
+        format::write_radix(w, Sign::Plus, self, radix)
+    }
+
+    /// Writes the integer formatted in the given radix to an [`io::Write`][std::io::Write],
+    /// like [`to_str_radix`][Self::to_str_radix], but without building the whole string in
+    /// memory first. `radix` must be in the range `2...36`.
+    ///
+    /// The digits are written in pieces, so an unbuffered writer may see many small writes.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_radix_to_io<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        radix: u32,
+    ) -> std::io::Result<()> {
+        format::write_radix_io(w, Sign::Plus, self, radix)
+    }
+
     /// Returns the integer in the requested base in big-endian digit order.
     /// The output is not given in a human readable alphabet but as a zero
     /// based `u8` number.
@@ -855,17 +1258,86 @@ impl BigUint {
         self.data.len() as u64 * u64::from(big_digit::BITS) - zeros
     }
 
+    /// Creates a zero [`BigUint`] with room for values of at least `bits` bits without
+    /// reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::with_capacity_bits(1000);
+    /// assert_eq!(n, BigUint::from(0u32));
+    /// assert!(n.capacity_bits() >= 1000);
+    /// ```
+    pub fn with_capacity_bits(bits: u64) -> Self {
+        BigUint {
+            data: DigitVec::with_capacity(digits_for_bits(bits)),
+        }
+    }
+
+    /// Returns the number of bits the [`BigUint`] can hold without reallocating.
+    #[inline]
+    pub fn capacity_bits(&self) -> u64 {
+        self.data.capacity() as u64 * u64::from(big_digit::BITS)
+    }
+
+    /// Reserves capacity for the [`BigUint`] to grow to at least `bits` bits without
+    /// reallocating. Does nothing if the capacity is already sufficient.
+    ///
+    /// Assignment operators like `+=` and `>>=` keep the capacity even when the value gets smaller,
+    /// until [`shrink_to_fit`][Self::shrink_to_fit] releases it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = BigUint::from(1u32);
+    /// n.reserve_bits(4096);
+    /// assert!(n.capacity_bits() >= 4096);
+    /// ```
+    pub fn reserve_bits(&mut self, bits: u64) {
+        let digits = digits_for_bits(bits);
+        if digits > self.data.len() {
+            self.data.reserve(digits - self.data.len());
+        }
+    }
+
+    /// Shrinks the capacity of the [`BigUint`] as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Sets the [`BigUint`] to zero, keeping its allocated capacity for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = BigUint::from(1u32) << 1000u32;
+    /// let capacity = n.capacity_bits();
+    /// n.clear();
+    /// assert_eq!(n, BigUint::from(0u32));
+    /// assert_eq!(n.capacity_bits(), capacity);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
     /// Strips off trailing zero bigdigits - comparisons require the last element in the vector to
     /// be nonzero.
+    ///
+    /// This never shrinks the allocation, so arithmetic keeps any capacity the caller reserved.
     #[inline]
     fn normalize(&mut self) {
         if let Some(&0) = self.data.last() {
             let len = self.data.iter().rposition(|&d| d != 0).map_or(0, |i| i + 1);
             self.data.truncate(len);
         }
-        if self.data.len() < self.data.capacity() / 4 {
-            self.data.shrink_to_fit();
-        }
     }
 
     /// Returns a normalized [`BigUint`].
@@ -880,6 +1352,108 @@ impl BigUint {
         Pow::pow(self, exponent)
     }
 
+    /// Returns `self ^ exponent`, or an error instead of aborting if the result can't be
+    /// allocated.
+    ///
+    /// Results that are too large to ever be allocated fail before any work is done. The
+    /// temporary buffers of the multiplications are allocated fallibly too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let two = BigUint::from(2u32);
+    /// assert_eq!(two.try_pow(100), Ok(BigUint::from(1u32) << 100u32));
+    /// ```
+    pub fn try_pow(&self, exponent: u32) -> Result<Self, AllocBigIntError> {
+        power::try_pow(self, exponent)
+    }
+
+    /// Returns `self * other`, or an error instead of aborting if the result can't be
+    /// allocated, including its temporary buffers.
+    pub fn try_mul(&self, other: &Self) -> Result<Self, AllocBigIntError> {
+        multiplication::try_mul(self, other)
+    }
+
+    /// Returns `self << bits`, or an error instead of aborting if the result can't be
+    /// allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let one = BigUint::from(1u32);
+    /// assert_eq!(one.try_shl(100), Ok(BigUint::from(1u32) << 100u32));
+    /// assert!(one.try_shl(u64::MAX).is_err());
+    /// ```
+    pub fn try_shl(&self, bits: u64) -> Result<Self, AllocBigIntError> {
+        shift::try_shl(self, bits)
+    }
+
+    /// Stores `a + b` in `out`, reusing its allocation.
+    ///
+    /// Together with the other `*_into` functions, this lets a hot loop keep its scratch values
+    /// around instead of allocating a fresh result for every operation. `out` only grows if its
+    /// capacity is too small for the result, and it never shrinks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let a = BigUint::from(u64::MAX);
+    /// let mut out = BigUint::with_capacity_bits(128);
+    /// BigUint::add_into(&mut out, &a, &a);
+    /// assert_eq!(out, BigUint::from(u64::MAX) * 2u32);
+    /// ```
+    pub fn add_into(out: &mut Self, a: &Self, b: &Self) {
+        addition::add_into(out, &a.data, &b.data)
+    }
+
+    /// Stores `a - b` in `out`, reusing its allocation. See [`BigUint::add_into`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is larger than `a`, like the `Sub` operator.
+    pub fn sub_into(out: &mut Self, a: &Self, b: &Self) {
+        subtraction::sub_into(out, &a.data, &b.data)
+    }
+
+    /// Stores `a * b` in `out`, reusing its allocation. See [`BigUint::add_into`].
+    ///
+    /// Only the result is written to `out`; the larger multiplication algorithms still allocate
+    /// their own temporary buffers.
+    pub fn mul_into(out: &mut Self, a: &Self, b: &Self) {
+        multiplication::mul_into(out, &a.data, &b.data)
+    }
+
+    /// Stores the quotient and remainder of `a / b` in `q` and `r`, reusing their allocations.
+    /// See [`BigUint::add_into`].
+    ///
+    /// When `b` isn't already normalized for long division, a shifted copy of it is still
+    /// allocated as a temporary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let a = BigUint::from(10u32).pow(40);
+    /// let b = BigUint::from(7u32).pow(20);
+    /// let (mut q, mut r) = (BigUint::default(), BigUint::default());
+    /// BigUint::div_rem_into(&mut q, &mut r, &a, &b);
+    /// assert_eq!((q, r), (&a / &b, &a % &b));
+    /// ```
+    pub fn div_rem_into(q: &mut Self, r: &mut Self, a: &Self, b: &Self) {
+        division::div_rem_into(q, r, a, b)
+    }
+
     /// Returns `(self ^ exponent) % modulus`.
     ///
     /// Panics if the modulus is zero.
@@ -1008,6 +1582,105 @@ impl BigUint {
         self.data.iter().map(|&d| u64::from(d.count_ones())).sum()
     }
 
+    /// Returns the number of zero bits among the lowest `width` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(0b1011u32);
+    /// assert_eq!(n.count_zeros(4), 1);
+    /// assert_eq!(n.count_zeros(64), 61);
+    /// ```
+    pub fn count_zeros(&self, width: u64) -> u64 {
+        bits::count_zeros(self, width)
+    }
+
+    /// Returns the number of leading zero bits among the lowest `width` bits, ignoring any bits
+    /// at or above `width`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(0x1234u32);
+    /// assert_eq!(n.leading_zeros_in(16), 3);
+    /// assert_eq!(n.leading_zeros_in(128), 115);
+    /// assert_eq!(n.leading_zeros_in(4), 1);
+    /// ```
+    pub fn leading_zeros_in(&self, width: u64) -> u64 {
+        match width.checked_sub(1).and_then(|top| self.prev_set_bit(top)) {
+            Some(bit) => width - 1 - bit,
+            None => width,
+        }
+    }
+
+    /// Returns an iterator of the positions of the one bits, in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = (BigUint::from(1u32) << 100u32) + 0b1010u32;
+    /// assert_eq!(n.iter_ones().collect::<Vec<u64>>(), vec![1, 3, 100]);
+    /// ```
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones::new(&self.data)
+    }
+
+    /// Returns an iterator of the positions of the zero bits below the most significant one bit,
+    /// in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(0b10110u32);
+    /// assert_eq!(n.iter_zeros().collect::<Vec<u64>>(), vec![0, 3]);
+    /// ```
+    pub fn iter_zeros(&self) -> Zeros<'_> {
+        Zeros::new(&self.data)
+    }
+
+    /// Returns the position of the lowest one bit at or above `from`, or `None` if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = (BigUint::from(1u32) << 100u32) + 0b1010u32;
+    /// assert_eq!(n.next_set_bit(0), Some(1));
+    /// assert_eq!(n.next_set_bit(3), Some(3));
+    /// assert_eq!(n.next_set_bit(4), Some(100));
+    /// assert_eq!(n.next_set_bit(101), None);
+    /// ```
+    pub fn next_set_bit(&self, from: u64) -> Option<u64> {
+        bits::next_set_bit(self, from)
+    }
+
+    /// Returns the position of the highest one bit at or below `from`, or `None` if there is
+    /// none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = (BigUint::from(1u32) << 100u32) + 0b1010u32;
+    /// assert_eq!(n.prev_set_bit(u64::MAX), Some(100));
+    /// assert_eq!(n.prev_set_bit(99), Some(3));
+    /// assert_eq!(n.prev_set_bit(2), Some(1));
+    /// assert_eq!(n.prev_set_bit(0), None);
+    /// ```
+    pub fn prev_set_bit(&self, from: u64) -> Option<u64> {
+        bits::prev_set_bit(self, from)
+    }
+
     /// Returns whether the bit in the given position is set
     pub fn bit(&self, bit: u64) -> bool {
         let bits_per_digit = u64::from(big_digit::BITS);
@@ -1042,6 +1715,270 @@ impl BigUint {
             self.normalize();
         }
     }
+
+    /// Returns the bits in `range` as a new [`BigUint`], shifted down so that bit `range.start`
+    /// becomes bit 0. Bits past the end of the number are zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(0xabcd_ef01u32);
+    /// assert_eq!(n.extract_bits(8..20), BigUint::from(0xdefu32));
+    /// assert_eq!(n.extract_bits(28..100), BigUint::from(0xau32));
+    /// ```
+    pub fn extract_bits(&self, range: Range<u64>) -> BigUint {
+        bits::extract_bits(self, range)
+    }
+
+    /// Replaces the bits in `range` with the low bits of `value`, ignoring any bits of `value`
+    /// that don't fit in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = BigUint::from(0xabcd_ef01u32);
+    /// n.deposit_bits(8..20, &BigUint::from(0x12345u32));
+    /// assert_eq!(n, BigUint::from(0xabc3_4501u32));
+    /// ```
+    pub fn deposit_bits(&mut self, range: Range<u64>, value: &BigUint) {
+        bits::deposit_bits(self, range, value);
+    }
+
+    /// Clears all of the bits in `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = BigUint::from(0xabcd_ef01u32);
+    /// n.clear_bits(4..28);
+    /// assert_eq!(n, BigUint::from(0xa000_0001u32));
+    /// ```
+    pub fn clear_bits(&mut self, range: Range<u64>) {
+        bits::clear_bits(self, range);
+    }
+
+    /// Sets all of the bits in `range`.
+    ///
+    /// Note that setting bits beyond the current bit length may need a reallocation to store
+    /// the new digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = BigUint::from(0xa000_0001u32);
+    /// n.set_bits(4..36);
+    /// assert_eq!(n, BigUint::from(0xf_ffff_fff1u64));
+    /// ```
+    pub fn set_bits(&mut self, range: Range<u64>) {
+        bits::set_bits(self, range);
+    }
+
+    /// Truncates the [`BigUint`] to its `bits` least significant bits, which is the same as
+    /// reducing it modulo 2<sup>`bits`</sup>.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = BigUint::from(0x1234_5678u32);
+    /// n.truncate_bits(12);
+    /// assert_eq!(n, BigUint::from(0x678u32));
+    /// ```
+    pub fn truncate_bits(&mut self, bits: u64) {
+        let bits_per_digit = u64::from(big_digit::BITS);
+        let digit_index = (bits / bits_per_digit).to_usize().unwrap_or(usize::MAX);
+        if digit_index < self.data.len() {
+            self.data.truncate(digit_index + 1);
+            self.data[digit_index] &= ((1 as BigDigit) << (bits % bits_per_digit)) - 1;
+            self.normalize();
+        }
+    }
+
+    /// Replaces the value with its two's-complement negation in `bits` bits, i.e.
+    /// 2<sup>`bits`</sup> minus the value, modulo 2<sup>`bits`</sup>.
+    pub(crate) fn negate_bits(&mut self, bits: u64) {
+        self.truncate_bits(bits);
+        if self.is_zero() {
+            return;
+        }
+        self.data.resize(digits_for_bits(bits), 0);
+        let mut carry = true;
+        for d in self.data.iter_mut() {
+            let (value, overflow) = (!*d).overflowing_add(BigDigit::from(carry));
+            *d = value;
+            carry = overflow;
+        }
+        // Mask off a partial top digit, and then the top digits may be zero either way.
+        self.truncate_bits(bits);
+        self.normalize();
+    }
+
+    /// Interprets the `bits` least significant bits of the [`BigUint`] as a two's-complement
+    /// number, sign-extending from bit `bits - 1`. Any higher bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigUint};
+    ///
+    /// assert_eq!(BigUint::from(0xffu32).to_signed_from_bits(8), BigInt::from(-1));
+    /// assert_eq!(BigUint::from(0x17fu32).to_signed_from_bits(8), BigInt::from(127));
+    /// ```
+    pub fn to_signed_from_bits(&self, bits: u64) -> BigInt {
+        let mut n = self.clone();
+        n.truncate_bits(bits);
+        if bits > 0 && n.bit(bits - 1) {
+            n.negate_bits(bits);
+            -BigInt::from(n)
+        } else {
+            BigInt::from(n)
+        }
+    }
+
+    /// Returns `(self + other)` modulo 2<sup>`bits`</sup>.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let a = BigUint::from(200u32);
+    /// assert_eq!(a.wrapping_add_bits(&BigUint::from(100u32), 8), BigUint::from(44u32));
+    /// ```
+    pub fn wrapping_add_bits(&self, other: &BigUint, bits: u64) -> BigUint {
+        let mut sum = self + other;
+        sum.truncate_bits(bits);
+        sum
+    }
+
+    /// Returns `(self - other)` modulo 2<sup>`bits`</sup>, wrapping around instead of
+    /// underflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let a = BigUint::from(100u32);
+    /// assert_eq!(a.wrapping_sub_bits(&BigUint::from(200u32), 8), BigUint::from(156u32));
+    /// ```
+    pub fn wrapping_sub_bits(&self, other: &BigUint, bits: u64) -> BigUint {
+        if self >= other {
+            let mut diff = self - other;
+            diff.truncate_bits(bits);
+            diff
+        } else {
+            let mut diff = other - self;
+            diff.negate_bits(bits);
+            diff
+        }
+    }
+
+    /// Returns `(self * other)` modulo 2<sup>`bits`</sup>.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let a = BigUint::from(200u32);
+    /// assert_eq!(a.wrapping_mul_bits(&BigUint::from(3u32), 8), BigUint::from(88u32));
+    /// ```
+    pub fn wrapping_mul_bits(&self, other: &BigUint, bits: u64) -> BigUint {
+        let mut product = self * other;
+        product.truncate_bits(bits);
+        product
+    }
+
+    /// Returns the bitwise complement of the `width` least significant bits, as if the value
+    /// were a `width`-bit unsigned integer. Any higher bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(0x0fu32).not_bits(8), BigUint::from(0xf0u32));
+    /// assert_eq!(BigUint::from(0x1ffu32).not_bits(8), BigUint::from(0u32));
+    /// ```
+    pub fn not_bits(&self, width: u64) -> BigUint {
+        bits::not_bits(self, width)
+    }
+
+    /// Rotates the `width` least significant bits left by `k` bits, wrapping the bits shifted
+    /// out at the top around to the bottom. Any higher bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(0x8001u32);
+    /// assert_eq!(n.rotate_left_bits(4, 16), BigUint::from(0x0018u32));
+    /// assert_eq!(n.rotate_left_bits(20, 16), BigUint::from(0x0018u32));
+    /// ```
+    pub fn rotate_left_bits(&self, k: u64, width: u64) -> BigUint {
+        bits::rotate_left_bits(self, k, width)
+    }
+
+    /// Rotates the `width` least significant bits right by `k` bits, wrapping the bits shifted
+    /// out at the bottom around to the top. Any higher bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(0x8001u32);
+    /// assert_eq!(n.rotate_right_bits(4, 16), BigUint::from(0x1800u32));
+    /// ```
+    pub fn rotate_right_bits(&self, k: u64, width: u64) -> BigUint {
+        if width == 0 {
+            return BigUint::ZERO;
+        }
+        bits::rotate_left_bits(self, width - k % width, width)
+    }
+
+    /// Reverses the order of the `width` least significant bits, so bit 0 becomes bit
+    /// `width - 1`. Any higher bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(0b1101u32).reverse_bits(6), BigUint::from(0b101100u32));
+    /// ```
+    pub fn reverse_bits(&self, width: u64) -> BigUint {
+        bits::reverse_bits(self, width)
+    }
+
+    /// Reverses the order of the bytes in the `width` least significant bits. Any higher bits
+    /// are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not a multiple of 8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(0x12_3456u32).swap_bytes(24), BigUint::from(0x56_3412u32));
+    /// ```
+    pub fn swap_bytes(&self, width: u64) -> BigUint {
+        bits::swap_bytes(self, width)
+    }
 }
 
 impl num_traits::FromBytes for BigUint {
@@ -1070,7 +2007,7 @@ impl num_traits::ToBytes for BigUint {
 
 pub(crate) trait IntDigits {
     fn digits(&self) -> &[BigDigit];
-    fn digits_mut(&mut self) -> &mut Vec<BigDigit>;
+    fn digits_mut(&mut self) -> &mut DigitVec;
     fn normalize(&mut self);
     fn capacity(&self) -> usize;
     fn len(&self) -> usize;
@@ -1082,7 +2019,7 @@ impl IntDigits for BigUint {
         &self.data
     }
     #[inline]
-    fn digits_mut(&mut self) -> &mut Vec<BigDigit> {
+    fn digits_mut(&mut self) -> &mut DigitVec {
         &mut self.data
     }
     #[inline]
@@ -1135,7 +2072,7 @@ cfg_digit!(
     #[test]
     fn test_from_slice() {
         fn check(slice: &[u32], data: &[BigDigit]) {
-            assert_eq!(BigUint::from_slice(slice).data, data);
+            assert_eq!(BigUint::from_slice(slice).data.as_slice(), data);
         }
         check(&[1], &[1]);
         check(&[0, 0, 0], &[]);
@@ -1149,7 +2086,7 @@ cfg_digit!(
     fn test_from_slice() {
         fn check(slice: &[u32], data: &[BigDigit]) {
             assert_eq!(
-                BigUint::from_slice(slice).data,
+                BigUint::from_slice(slice).data.as_slice(),
                 data,
                 "from {:?}, to {:?}",
                 slice,
@@ -1,6 +1,11 @@
-use super::{BigUint, IntDigits};
+use super::{biguint_from_vec, BigUint, DigitVec, IntDigits};
 
-use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use crate::big_digit::{self, BigDigit};
+use crate::UsizePromotion;
+
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Range};
+use num_integer::Integer;
+use num_traits::{ToPrimitive, Zero};
 
 forward_val_val_binop!(impl BitAnd for BigUint, bitand);
 forward_ref_val_binop!(impl BitAnd for BigUint, bitand);
@@ -91,3 +96,284 @@ impl BitXorAssign<&Self> for BigUint {
         self.normalize();
     }
 }
+
+macro_rules! impl_scalar_bitops {
+    ($($scalar:ty),*) => {$(
+        forward_all_scalar_binop_to_ref_val!(impl BitAnd<$scalar> for BigUint, bitand);
+        forward_all_scalar_binop_to_val_val_commutative!(impl BitOr<$scalar> for BigUint, bitor);
+        forward_all_scalar_binop_to_val_val_commutative!(impl BitXor<$scalar> for BigUint, bitxor);
+
+        // The result fits in the scalar, so only its low digits are needed.
+        impl BitAnd<$scalar> for &BigUint {
+            type Output = BigUint;
+
+            #[allow(clippy::cast_lossless)]
+            #[inline]
+            fn bitand(self, other: $scalar) -> BigUint {
+                let (digits, len) = big_digit::from_u128(other as u128);
+                let data = self.data.iter().zip(&digits[..len]).map(|(&a, &b)| a & b);
+                biguint_from_vec(data.collect::<DigitVec>())
+            }
+        }
+
+        impl BitAnd<&BigUint> for $scalar {
+            type Output = BigUint;
+
+            #[inline]
+            fn bitand(self, other: &BigUint) -> BigUint {
+                other & self
+            }
+        }
+
+        impl BitAndAssign<$scalar> for BigUint {
+            #[allow(clippy::cast_lossless)]
+            #[inline]
+            fn bitand_assign(&mut self, other: $scalar) {
+                let (digits, len) = big_digit::from_u128(other as u128);
+                self.data.truncate(len);
+                for (ai, &bi) in self.data.iter_mut().zip(digits.iter()) {
+                    *ai &= bi;
+                }
+                self.normalize();
+            }
+        }
+
+        impl BitOr<$scalar> for BigUint {
+            type Output = BigUint;
+
+            #[inline]
+            fn bitor(mut self, other: $scalar) -> BigUint {
+                self |= other;
+                self
+            }
+        }
+
+        impl BitOrAssign<$scalar> for BigUint {
+            #[allow(clippy::cast_lossless)]
+            #[inline]
+            fn bitor_assign(&mut self, other: $scalar) {
+                let (digits, len) = big_digit::from_u128(other as u128);
+                if self.data.len() < len {
+                    self.data.resize(len, 0);
+                }
+                for (ai, &bi) in self.data.iter_mut().zip(&digits[..len]) {
+                    *ai |= bi;
+                }
+            }
+        }
+
+        impl BitXor<$scalar> for BigUint {
+            type Output = BigUint;
+
+            #[inline]
+            fn bitxor(mut self, other: $scalar) -> BigUint {
+                self ^= other;
+                self
+            }
+        }
+
+        impl BitXorAssign<$scalar> for BigUint {
+            #[allow(clippy::cast_lossless)]
+            #[inline]
+            fn bitxor_assign(&mut self, other: $scalar) {
+                let (digits, len) = big_digit::from_u128(other as u128);
+                if self.data.len() < len {
+                    self.data.resize(len, 0);
+                }
+                for (ai, &bi) in self.data.iter_mut().zip(&digits[..len]) {
+                    *ai ^= bi;
+                }
+                self.normalize();
+            }
+        }
+    )*};
+}
+
+impl_scalar_bitops!(u32, u64, u128);
+promote_unsigned_scalars!(impl BitAnd for BigUint, bitand);
+promote_unsigned_scalars_assign!(impl BitAndAssign for BigUint, bitand_assign);
+promote_unsigned_scalars!(impl BitOr for BigUint, bitor);
+promote_unsigned_scalars_assign!(impl BitOrAssign for BigUint, bitor_assign);
+promote_unsigned_scalars!(impl BitXor for BigUint, bitxor);
+promote_unsigned_scalars_assign!(impl BitXorAssign for BigUint, bitxor_assign);
+
+pub(super) fn extract_bits(u: &BigUint, range: Range<u64>) -> BigUint {
+    if range.start >= range.end || range.start >= u.bits() {
+        return BigUint::ZERO;
+    }
+    let bits_per_digit = u64::from(big_digit::BITS);
+    // `start` is within the data, so this index is too.
+    let first = (range.start / bits_per_digit) as usize;
+    let last = Integer::div_ceil(&range.end, &bits_per_digit)
+        .to_usize()
+        .map_or(u.data.len(), |last| Ord::min(last, u.data.len()));
+
+    let mut field = biguint_from_vec(u.data[first..last].to_vec());
+    field >>= range.start % bits_per_digit;
+    field.truncate_bits(range.end - range.start);
+    field
+}
+
+/// Calls `f` with each digit that overlaps the bit range and the mask of the range's bits
+/// within it, stopping at the end of the data.
+fn for_each_masked(data: &mut [BigDigit], range: Range<u64>, f: impl Fn(&mut BigDigit, BigDigit)) {
+    let bits_per_digit = u64::from(big_digit::BITS);
+    let mut bit = range.start;
+    while bit < range.end {
+        let index = (bit / bits_per_digit).to_usize().unwrap_or(usize::MAX);
+        let digit = match data.get_mut(index) {
+            Some(digit) => digit,
+            None => break,
+        };
+        let shift = bit % bits_per_digit;
+        let width = Ord::min(bits_per_digit - shift, range.end - bit);
+        f(digit, (BigDigit::MAX >> (bits_per_digit - width)) << shift);
+        bit += width;
+    }
+}
+
+pub(super) fn clear_bits(u: &mut BigUint, range: Range<u64>) {
+    for_each_masked(&mut u.data, range, |digit, mask| *digit &= !mask);
+    u.normalize();
+}
+
+pub(super) fn set_bits(u: &mut BigUint, range: Range<u64>) {
+    if range.start >= range.end {
+        return;
+    }
+    // Saturating like `set_bit`, as any such case is guaranteed to fail allocation.
+    let len = Integer::div_ceil(&range.end, &u64::from(big_digit::BITS));
+    let len = len.to_usize().unwrap_or(usize::MAX);
+    if u.data.len() < len {
+        u.data.resize(len, 0);
+    }
+    for_each_masked(&mut u.data, range, |digit, mask| *digit |= mask);
+}
+
+pub(super) fn deposit_bits(u: &mut BigUint, range: Range<u64>, value: &BigUint) {
+    if range.start >= range.end {
+        return;
+    }
+    clear_bits(u, range.clone());
+    let field = extract_bits(value, 0..range.end - range.start);
+    if field.is_zero() {
+        return;
+    }
+
+    let bits_per_digit = u64::from(big_digit::BITS);
+    let first = (range.start / bits_per_digit)
+        .to_usize()
+        .unwrap_or(usize::MAX);
+    let shift = (range.start % bits_per_digit) as u8;
+    let len = first.saturating_add(field.data.len() + 1);
+    if u.data.len() < len {
+        u.data.resize(len, 0);
+    }
+    for (i, &digit) in field.data.iter().enumerate() {
+        u.data[first + i] |= digit << shift;
+        if shift > 0 {
+            u.data[first + i + 1] |= digit >> (big_digit::BITS - shift);
+        }
+    }
+    u.normalize();
+}
+
+pub(super) fn next_set_bit(u: &BigUint, from: u64) -> Option<u64> {
+    let bits_per_digit = u64::from(big_digit::BITS);
+    let index = (from / bits_per_digit).to_usize()?;
+    let first = u.data.get(index)? & (BigDigit::MAX << (from % bits_per_digit));
+    if first != 0 {
+        return Some(index as u64 * bits_per_digit + u64::from(first.trailing_zeros()));
+    }
+    let rest = &u.data[index + 1..];
+    let i = rest.iter().position(|&digit| digit != 0)?;
+    let zeros = u64::from(rest[i].trailing_zeros());
+    Some((index + 1 + i) as u64 * bits_per_digit + zeros)
+}
+
+pub(super) fn prev_set_bit(u: &BigUint, from: u64) -> Option<u64> {
+    let bits = u.bits();
+    if from >= bits {
+        return bits.checked_sub(1);
+    }
+    let bits_per_digit = u64::from(big_digit::BITS);
+    let index = (from / bits_per_digit) as usize;
+    let first = u.data[index] & (BigDigit::MAX >> (bits_per_digit - 1 - from % bits_per_digit));
+    if first != 0 {
+        let zeros = u64::from(first.leading_zeros());
+        return Some((index as u64 + 1) * bits_per_digit - 1 - zeros);
+    }
+    let i = u.data[..index].iter().rposition(|&digit| digit != 0)?;
+    let zeros = u64::from(u.data[i].leading_zeros());
+    Some((i as u64 + 1) * bits_per_digit - 1 - zeros)
+}
+
+pub(super) fn count_zeros(u: &BigUint, width: u64) -> u64 {
+    let bits_per_digit = u64::from(big_digit::BITS);
+    let full = (width / bits_per_digit).to_usize().unwrap_or(usize::MAX);
+    let mut ones: u64 = u
+        .data
+        .iter()
+        .take(full)
+        .map(|&d| u64::from(d.count_ones()))
+        .sum();
+    if let Some(&digit) = u.data.get(full) {
+        let mask = !(BigDigit::MAX << (width % bits_per_digit));
+        ones += u64::from((digit & mask).count_ones());
+    }
+    width - ones
+}
+
+/// Returns the low `width` bits of `u` as digits, zero-extended to exactly the number of digits
+/// needed for `width` bits.
+fn digits_in(u: &BigUint, width: u64) -> DigitVec {
+    let mut x = u.clone();
+    x.truncate_bits(width);
+    x.data.resize(super::digits_for_bits(width), 0);
+    x.data
+}
+
+pub(super) fn not_bits(u: &BigUint, width: u64) -> BigUint {
+    let mut data = digits_in(u, width);
+    for d in data.iter_mut() {
+        *d = !*d;
+    }
+    let mut x = BigUint { data };
+    // Mask off a partial top digit, and then the top digits may be zero either way.
+    x.truncate_bits(width);
+    x.normalized()
+}
+
+pub(super) fn rotate_left_bits(u: &BigUint, k: u64, width: u64) -> BigUint {
+    if width == 0 {
+        return BigUint::ZERO;
+    }
+    let k = k % width;
+    let mut x = u.clone();
+    x.truncate_bits(width);
+    let lo = &x >> (width - k);
+    x <<= k;
+    x.truncate_bits(width);
+    x | lo
+}
+
+/// Reverses the order of the digits in the low `width` bits, applies `f` to each digit, and
+/// then shifts out the low padding that came from above `width`.
+fn reverse_digits(u: &BigUint, width: u64, f: fn(BigDigit) -> BigDigit) -> BigUint {
+    let mut data = digits_in(u, width);
+    data.reverse();
+    for d in data.iter_mut() {
+        *d = f(*d);
+    }
+    let padding = data.len() as u64 * u64::from(big_digit::BITS) - width;
+    biguint_from_vec(data) >> padding
+}
+
+pub(super) fn reverse_bits(u: &BigUint, width: u64) -> BigUint {
+    reverse_digits(u, width, BigDigit::reverse_bits)
+}
+
+pub(super) fn swap_bytes(u: &BigUint, width: u64) -> BigUint {
+    assert!(width % 8 == 0, "width must be a multiple of 8");
+    reverse_digits(u, width, BigDigit::swap_bytes)
+}
@@ -1,13 +1,13 @@
 // This uses stdlib features higher than the MSRV
 #![allow(clippy::manual_range_contains)] // 1.35
 
-use super::{biguint_from_vec, BigUint, ToBigUint};
+use super::{biguint_from_vec, BigUint, DigitVec, ToBigUint};
 
 use super::addition::add2;
-use super::division::{div_rem_digit, FAST_DIV_WIDE};
-use super::multiplication::mac_with_carry;
+use super::division::div_rem_digit;
 
 use crate::big_digit::{self, BigDigit};
+use crate::digits::{mac_with_carry, FAST_DIV_WIDE};
 use crate::ParseBigIntError;
 use crate::TryFromBigIntError;
 
@@ -47,7 +47,7 @@ pub(super) fn from_bitwise_digits_le(v: &[u8], bits: u8) -> BigUint {
 
     let digits_per_big_digit = big_digit::BITS / bits;
 
-    let data = v
+    let data: DigitVec = v
         .chunks(digits_per_big_digit.into())
         .map(|chunk| {
             chunk
@@ -219,81 +219,99 @@ impl Num for BigUint {
 
     /// Creates and initializes a `BigUint`.
     fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
-        assert!(2 <= radix && radix <= 36, "The radix must be within 2...36");
-        let mut s = s;
-        if let Some(tail) = s.strip_prefix('+') {
-            if !tail.starts_with('+') {
-                s = tail
-            }
-        }
+        from_str_radix(s, radix, u64::MAX)
+    }
+}
 
-        if s.is_empty() {
-            return Err(ParseBigIntError::empty());
+/// Parses like `from_str_radix`, failing with `TooLong` if the value has more than `max_bits`
+/// bits. Inputs that are clearly too long are rejected before doing the quadratic work.
+pub(super) fn from_str_radix(
+    s: &str,
+    radix: u32,
+    max_bits: u64,
+) -> Result<BigUint, ParseBigIntError> {
+    if !(2..=36).contains(&radix) {
+        return Err(ParseBigIntError::invalid_radix());
+    }
+    if let Some(tail) = s.strip_prefix('+') {
+        if !tail.starts_with('+') {
+            return from_str_radix(tail, radix, max_bits).map_err(ParseBigIntError::after_sign);
         }
+    }
 
-        if s.starts_with('_') {
-            // Must lead with a real digit!
-            return Err(ParseBigIntError::invalid());
-        }
+    if s.is_empty() {
+        return Err(ParseBigIntError::empty());
+    }
 
-        // First normalize all characters to plain digit values
-        let mut v = Vec::with_capacity(s.len());
-        for b in s.bytes() {
-            let d = match b {
-                b'0'..=b'9' => b - b'0',
-                b'a'..=b'z' => b - b'a' + 10,
-                b'A'..=b'Z' => b - b'A' + 10,
-                b'_' => continue,
-                _ => u8::MAX,
-            };
-            if d < radix as u8 {
-                v.push(d);
-            } else {
-                return Err(ParseBigIntError::invalid());
-            }
+    if s.starts_with('_') {
+        // Must lead with a real digit!
+        return Err(ParseBigIntError::misplaced_underscore(0));
+    }
+
+    // First normalize all characters to plain digit values
+    let mut v = Vec::with_capacity(s.len());
+    for (i, b) in s.bytes().enumerate() {
+        let d = match b {
+            b'0'..=b'9' => b - b'0',
+            b'a'..=b'z' => b - b'a' + 10,
+            b'A'..=b'Z' => b - b'A' + 10,
+            b'_' => continue,
+            _ => u8::MAX,
+        };
+        if d < radix as u8 {
+            v.push(d);
+        } else {
+            return Err(ParseBigIntError::invalid(i));
         }
+    }
 
-        let res = if radix.is_power_of_two() {
-            // Powers of two can use bitwise masks and shifting instead of multiplication
-            let bits = ilog2(radix);
-            v.reverse();
-            if big_digit::BITS % bits == 0 {
-                from_bitwise_digits_le(&v, bits)
-            } else {
-                from_inexact_bitwise_digits_le(&v, bits)
-            }
+    // A value with `n` significant digits is at least `radix^(n - 1)`.
+    let significant = v.iter().position(|&d| d != 0).map_or(0, |i| v.len() - i);
+    let min_bits = (significant.saturating_sub(1) as u64).saturating_mul(u64::from(ilog2(radix)));
+    if significant > 0 && min_bits >= max_bits {
+        return Err(ParseBigIntError::too_long());
+    }
+
+    let res = if radix.is_power_of_two() {
+        // Powers of two can use bitwise masks and shifting instead of multiplication
+        let bits = ilog2(radix);
+        v.reverse();
+        if big_digit::BITS % bits == 0 {
+            from_bitwise_digits_le(&v, bits)
         } else {
-            from_radix_digits_be(&v, radix)
-        };
-        Ok(res)
+            from_inexact_bitwise_digits_le(&v, bits)
+        }
+    } else {
+        from_radix_digits_be(&v, radix)
+    };
+    if res.bits() > max_bits {
+        return Err(ParseBigIntError::too_long());
     }
+    Ok(res)
 }
 
-fn high_bits_to_u64(v: &BigUint) -> u64 {
-    match v.data.len() {
+/// Returns the 64 most significant bits of the `bits`-bit value in `digits`, rounded to odd.
+///
+/// This is generic over the digit width so that borrowed views can share it.
+fn high_bits_to_u64<T: PrimInt + Into<u64>>(digits: &[T], bits: u64) -> u64 {
+    let digit_width = u64::from(T::zero().count_zeros());
+    match digits.len() {
         0 => 0,
-        1 => {
-            // XXX Conversion is useless if already 64-bit.
-            #[allow(clippy::useless_conversion)]
-            let v0 = u64::from(v.data[0]);
-            v0
-        }
+        1 => digits[0].into(),
         _ => {
-            let mut bits = v.bits();
+            let mut bits = bits;
             let mut ret = 0u64;
             let mut ret_bits = 0;
 
-            for d in v.data.iter().rev() {
-                let digit_bits = (bits - 1) % u64::from(big_digit::BITS) + 1;
+            for &d in digits.iter().rev() {
+                let digit_bits = (bits - 1) % digit_width + 1;
                 let bits_want = Ord::min(64 - ret_bits, digit_bits);
 
                 if bits_want != 0 {
                     if bits_want != 64 {
                         ret <<= bits_want;
                     }
-                    // XXX Conversion is useless if already 64-bit.
-                    #[allow(clippy::useless_conversion)]
-                    let d0 = u64::from(*d) >> (digit_bits - bits_want);
+                    let d0 = d.into() >> (digit_bits - bits_want);
                     ret |= d0;
                 }
 
@@ -304,9 +322,7 @@ fn high_bits_to_u64(v: &BigUint) -> u64 {
                 // See: https://en.wikipedia.org/wiki/Rounding#Rounding_to_prepare_for_shorter_precision
 
                 if digit_bits - bits_want != 0 {
-                    // XXX Conversion is useless if already 64-bit.
-                    #[allow(clippy::useless_conversion)]
-                    let masked = u64::from(*d) << (64 - (digit_bits - bits_want) as u32);
+                    let masked = d.into() << (64 - (digit_bits - bits_want) as u32);
                     ret |= (masked != 0) as u64;
                 }
 
@@ -319,6 +335,30 @@ fn high_bits_to_u64(v: &BigUint) -> u64 {
     }
 }
 
+/// Converts the `bits`-bit value in `digits` to the nearest `f32`, or infinity.
+pub(super) fn digits_to_f32<T: PrimInt + Into<u64>>(digits: &[T], bits: u64) -> f32 {
+    let mantissa = high_bits_to_u64(digits, bits);
+    let exponent = bits - u64::from(fls(mantissa));
+
+    if exponent > f32::MAX_EXP as u64 {
+        f32::INFINITY
+    } else {
+        (mantissa as f32) * 2.0f32.powi(exponent as i32)
+    }
+}
+
+/// Converts the `bits`-bit value in `digits` to the nearest `f64`, or infinity.
+pub(super) fn digits_to_f64<T: PrimInt + Into<u64>>(digits: &[T], bits: u64) -> f64 {
+    let mantissa = high_bits_to_u64(digits, bits);
+    let exponent = bits - u64::from(fls(mantissa));
+
+    if exponent > f64::MAX_EXP as u64 {
+        f64::INFINITY
+    } else {
+        (mantissa as f64) * 2.0f64.powi(exponent as i32)
+    }
+}
+
 impl ToPrimitive for BigUint {
     #[inline]
     fn to_i64(&self) -> Option<i64> {
@@ -368,26 +408,12 @@ impl ToPrimitive for BigUint {
 
     #[inline]
     fn to_f32(&self) -> Option<f32> {
-        let mantissa = high_bits_to_u64(self);
-        let exponent = self.bits() - u64::from(fls(mantissa));
-
-        if exponent > f32::MAX_EXP as u64 {
-            Some(f32::INFINITY)
-        } else {
-            Some((mantissa as f32) * 2.0f32.powi(exponent as i32))
-        }
+        Some(digits_to_f32(&self.data, self.bits()))
     }
 
     #[inline]
     fn to_f64(&self) -> Option<f64> {
-        let mantissa = high_bits_to_u64(self);
-        let exponent = self.bits() - u64::from(fls(mantissa));
-
-        if exponent > f64::MAX_EXP as u64 {
-            Some(f64::INFINITY)
-        } else {
-            Some((mantissa as f64) * 2.0f64.powi(exponent as i32))
-        }
+        Some(digits_to_f64(&self.data, self.bits()))
     }
 }
 
@@ -597,6 +623,19 @@ impl From<bool> for BigUint {
     }
 }
 
+/// Writes the little-endian bytes to fill the buffer exactly, or fails if they don't fit.
+pub(super) fn write_bytes_le(u: &BigUint, buf: &mut [u8]) -> Result<(), TryFromBigIntError<()>> {
+    if u.bits() > buf.len() as u64 * 8 {
+        return Err(TryFromBigIntError::new(()));
+    }
+    let mut digits = u.data.iter();
+    for chunk in buf.chunks_mut(mem::size_of::<BigDigit>()) {
+        let digit = digits.next().copied().unwrap_or(0);
+        chunk.copy_from_slice(&digit.to_le_bytes()[..chunk.len()]);
+    }
+    Ok(())
+}
+
 // Extract bitwise digits that evenly divide BigDigit
 pub(super) fn to_bitwise_digits_le(u: &BigUint, bits: u8) -> Vec<u8> {
     debug_assert!(!u.is_zero() && bits <= 8 && big_digit::BITS % bits == 0);
@@ -638,7 +677,7 @@ fn to_inexact_bitwise_digits_le(u: &BigUint, bits: u8) -> Vec<u8> {
     let mut r = 0;
     let mut rbits = 0;
 
-    for c in &u.data {
+    for c in u.data.iter() {
         r |= *c << rbits;
         rbits += big_digit::BITS;
 
@@ -666,25 +705,12 @@ fn to_inexact_bitwise_digits_le(u: &BigUint, bits: u8) -> Vec<u8> {
     res
 }
 
-// Extract little-endian radix digits
+// Extract little-endian chunks of `power` radix digits each, returning the chunks and `power`.
+// The most significant chunk is not reduced, so it may have more than `power` digits.
 #[inline(always)] // forced inline to get const-prop for radix=10
-pub(super) fn to_radix_digits_le(u: &BigUint, radix: u32) -> Vec<u8> {
+fn to_radix_chunks_le(u: &BigUint, radix: u32) -> (Vec<BigDigit>, usize) {
     debug_assert!(!u.is_zero() && !radix.is_power_of_two());
 
-    #[cfg(feature = "std")]
-    let radix_digits = {
-        let radix_log2 = f64::from(radix).log2();
-        ((u.bits() as f64) / radix_log2).ceil()
-    };
-    #[cfg(not(feature = "std"))]
-    let radix_digits = {
-        let radix_log2 = ilog2(radix) as usize;
-        ((u.bits() as usize) / radix_log2) + 1
-    };
-
-    // Estimate how big the result will be, so we can pre-allocate it.
-    let mut res = Vec::with_capacity(radix_digits.to_usize().unwrap_or(0));
-
     let mut digits = u.clone();
 
     // X86 DIV can quickly divide by a full digit, otherwise we choose a divisor
@@ -694,7 +720,9 @@ pub(super) fn to_radix_digits_le(u: &BigUint, radix: u32) -> Vec<u8> {
     } else {
         get_half_radix_base(radix)
     };
-    let radix = radix as BigDigit;
+
+    // Every chunk but the last will be less than `base`, which is at least half a digit.
+    let mut res = Vec::with_capacity(digits.data.len() * 2 + 1);
 
     // For very large numbers, the O(n²) loop of repeated `div_rem_digit` dominates the
     // performance. We can mitigate this by dividing into chunks of a larger base first.
@@ -719,26 +747,55 @@ pub(super) fn to_radix_digits_le(u: &BigUint, radix: u32) -> Vec<u8> {
 
             // This inner loop now has O(√n²)=O(n) behavior altogether.
             for _ in 0..big_power {
-                let (q, mut r) = div_rem_digit(big_r, base);
+                let (q, r) = div_rem_digit(big_r, base);
                 big_r = q;
-                for _ in 0..power {
-                    res.push((r % radix) as u8);
-                    r /= radix;
-                }
+                res.push(r);
             }
         }
     }
 
     while digits.data.len() > 1 {
-        let (q, mut r) = div_rem_digit(digits, base);
+        let (q, r) = div_rem_digit(digits, base);
+        res.push(r);
+        digits = q;
+    }
+
+    res.push(digits.data[0]);
+    (res, power)
+}
+
+// Extract little-endian radix digits
+#[inline(always)] // forced inline to get const-prop for radix=10
+pub(super) fn to_radix_digits_le(u: &BigUint, radix: u32) -> Vec<u8> {
+    debug_assert!(!u.is_zero() && !radix.is_power_of_two());
+
+    #[cfg(feature = "std")]
+    let radix_digits = {
+        let radix_log2 = f64::from(radix).log2();
+        ((u.bits() as f64) / radix_log2).ceil()
+    };
+    #[cfg(not(feature = "std"))]
+    let radix_digits = {
+        let radix_log2 = ilog2(radix) as usize;
+        ((u.bits() as usize) / radix_log2) + 1
+    };
+
+    // Estimate how big the result will be, so we can pre-allocate it.
+    let mut res = Vec::with_capacity(radix_digits.to_usize().unwrap_or(0));
+
+    let (chunks, power) = to_radix_chunks_le(u, radix);
+    let (&last, chunks) = chunks.split_last().unwrap();
+    let radix = radix as BigDigit;
+
+    for &chunk in chunks {
+        let mut r = chunk;
         for _ in 0..power {
             res.push((r % radix) as u8);
             r /= radix;
         }
-        digits = q;
     }
 
-    let mut r = digits.data[0];
+    let mut r = last;
     while r != 0 {
         res.push((r % radix) as u8);
         r /= radix;
@@ -747,6 +804,133 @@ pub(super) fn to_radix_digits_le(u: &BigUint, radix: u32) -> Vec<u8> {
     res
 }
 
+/// The ASCII digits of a [`BigUint`] in some radix, which can be written most significant first
+/// without building a string of the whole number.
+pub(crate) struct RadixDigits<'a> {
+    u: &'a BigUint,
+    radix: u32,
+    // For radixes that aren't a power of two, the digits in little-endian chunks of `power`
+    // digits each. Powers of two are read directly from the bits of `u` instead.
+    chunks: Vec<BigDigit>,
+    power: usize,
+    len: usize,
+}
+
+impl<'a> RadixDigits<'a> {
+    pub(crate) fn new(u: &'a BigUint, radix: u32) -> Self {
+        assert!(2 <= radix && radix <= 36, "The radix must be within 2...36");
+
+        let mut digits = RadixDigits {
+            u,
+            radix,
+            chunks: Vec::new(),
+            power: 0,
+            len: 1,
+        };
+
+        if u.is_zero() {
+            // just "0"
+        } else if radix.is_power_of_two() {
+            let bits = u64::from(ilog2(radix));
+            digits.len = Integer::div_ceil(&u.bits(), &bits)
+                .to_usize()
+                .unwrap_or(usize::MAX);
+        } else {
+            let (chunks, power) = if radix == 10 {
+                // 10 is so common that it's worth separating out for const-propagation.
+                to_radix_chunks_le(u, 10)
+            } else {
+                to_radix_chunks_le(u, radix)
+            };
+            let mut last = *chunks.last().unwrap();
+            let mut last_len = 0;
+            while last != 0 {
+                last /= radix as BigDigit;
+                last_len += 1;
+            }
+            digits.len = (chunks.len() - 1) * power + last_len;
+            digits.chunks = chunks;
+            digits.power = power;
+        }
+
+        digits
+    }
+
+    /// Returns the total number of digits.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Writes all of the ASCII digits, most significant first, in pieces to `emit`.
+    pub(crate) fn write<E, F>(&self, uppercase: bool, mut emit: F) -> Result<(), E>
+    where
+        F: FnMut(&str) -> Result<(), E>,
+    {
+        let table: &[u8; 36] = if uppercase {
+            b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+        } else {
+            b"0123456789abcdefghijklmnopqrstuvwxyz"
+        };
+
+        // Digits are staged in a buffer, at most one chunk of a `BigDigit` (64 bits) at a time.
+        let mut buf = [0u8; 256];
+        let mut len = 0;
+        let mut flush = |buf: &[u8]| {
+            debug_assert!(buf.is_ascii());
+            emit(unsafe { core::str::from_utf8_unchecked(buf) })
+        };
+
+        if self.u.is_zero() {
+            return flush(b"0");
+        }
+
+        if self.radix.is_power_of_two() {
+            let bits = ilog2(self.radix);
+            let mask: BigDigit = (1 << bits) - 1;
+            let data = &self.u.data;
+            for i in (0..self.len as u64).rev() {
+                if len == buf.len() {
+                    flush(&buf)?;
+                    len = 0;
+                }
+                let bit = i * u64::from(bits);
+                let index = (bit / u64::from(big_digit::BITS)) as usize;
+                let offset = (bit % u64::from(big_digit::BITS)) as u8;
+                let mut d = data[index] >> offset;
+                if offset + bits > big_digit::BITS {
+                    if let Some(&hi) = data.get(index + 1) {
+                        d |= hi << (big_digit::BITS - offset);
+                    }
+                }
+                buf[len] = table[(d & mask) as usize];
+                len += 1;
+            }
+        } else {
+            let radix = self.radix as BigDigit;
+            let (&last, chunks) = self.chunks.split_last().unwrap();
+            let last_len = self.len - chunks.len() * self.power;
+            let mut write_chunk = |mut r: BigDigit, width: usize| {
+                if len + width > buf.len() {
+                    flush(&buf[..len])?;
+                    len = 0;
+                }
+                for b in buf[len..len + width].iter_mut().rev() {
+                    *b = table[(r % radix) as usize];
+                    r /= radix;
+                }
+                len += width;
+                Ok(())
+            };
+            write_chunk(last, last_len)?;
+            for &chunk in chunks.iter().rev() {
+                write_chunk(chunk, self.power)?;
+            }
+        }
+
+        flush(&buf[..len])
+    }
+}
+
 pub(super) fn to_radix_le(u: &BigUint, radix: u32) -> Vec<u8> {
     if u.is_zero() {
         vec![0]
@@ -790,7 +974,7 @@ pub(crate) fn to_str_radix_reversed(u: &BigUint, radix: u32) -> Vec<u8> {
 
 /// Returns the greatest power of the radix for the `BigDigit` bit size
 #[inline]
-fn get_radix_base(radix: u32) -> (BigDigit, usize) {
+pub(super) fn get_radix_base(radix: u32) -> (BigDigit, usize) {
     static BASES: [(BigDigit, usize); 257] = generate_radix_bases(big_digit::MAX);
     debug_assert!(!radix.is_power_of_two());
     debug_assert!((3..256).contains(&radix));
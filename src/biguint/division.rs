@@ -1,7 +1,8 @@
-use super::addition::__add2;
+use super::shift::shr_bits;
 use super::{cmp_slice, BigUint};
 
-use crate::big_digit::{self, BigDigit, DoubleBigDigit};
+use crate::big_digit::{self, BigDigit};
+use crate::digits::{__div_rem, __div_rem_digit, __rem_digit};
 use crate::UsizePromotion;
 
 use core::cmp::Ordering::{Equal, Greater, Less};
@@ -10,99 +11,13 @@ use core::ops::{Div, DivAssign, Rem, RemAssign};
 use num_integer::Integer;
 use num_traits::{CheckedDiv, CheckedEuclid, Euclid, One, ToPrimitive, Zero};
 
-pub(super) const FAST_DIV_WIDE: bool = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
-
-/// Divide a two digit numerator by a one digit divisor, returns quotient and remainder:
-///
-/// Note: the caller must ensure that both the quotient and remainder will fit into a single digit.
-/// This is _not_ true for an arbitrary numerator/denominator.
-///
-/// (This function also matches what the x86 divide instruction does).
-#[cfg(any(miri, not(any(target_arch = "x86", target_arch = "x86_64"))))]
-#[inline]
-fn div_wide(hi: BigDigit, lo: BigDigit, divisor: BigDigit) -> (BigDigit, BigDigit) {
-    debug_assert!(hi < divisor);
-
-    let lhs = big_digit::to_doublebigdigit(hi, lo);
-    let rhs = DoubleBigDigit::from(divisor);
-    ((lhs / rhs) as BigDigit, (lhs % rhs) as BigDigit)
-}
-
-/// x86 and x86_64 can use a real `div` instruction.
-#[cfg(all(not(miri), any(target_arch = "x86", target_arch = "x86_64")))]
-#[inline]
-fn div_wide(hi: BigDigit, lo: BigDigit, divisor: BigDigit) -> (BigDigit, BigDigit) {
-    // This debug assertion covers the potential #DE for divisor==0 or a quotient too large for one
-    // register, otherwise in release mode it will become a target-specific fault like SIGFPE.
-    // This should never occur with the inputs from our few `div_wide` callers.
-    debug_assert!(hi < divisor);
-
-    // SAFETY: The `div` instruction only affects registers, reading the explicit operand as the
-    // divisor, and implicitly reading RDX:RAX or EDX:EAX as the dividend. The result is implicitly
-    // written back to RAX or EAX for the quotient and RDX or EDX for the remainder. No memory is
-    // used, and flags are not preserved.
-    unsafe {
-        let (div, rem);
-
-        cfg_digit!(
-            macro_rules! div {
-                () => {
-                    "div {0:e}"
-                };
-            }
-            macro_rules! div {
-                () => {
-                    "div {0:r}"
-                };
-            }
-        );
-
-        core::arch::asm!(
-            div!(),
-            in(reg) divisor,
-            inout("dx") hi => rem,
-            inout("ax") lo => div,
-            options(pure, nomem, nostack),
-        );
-
-        (div, rem)
-    }
-}
-
-/// For small divisors, we can divide without promoting to `DoubleBigDigit` by
-/// using half-size pieces of digit, like long-division.
-#[inline]
-fn div_half(rem: BigDigit, digit: BigDigit, divisor: BigDigit) -> (BigDigit, BigDigit) {
-    use crate::big_digit::{HALF, HALF_BITS};
-
-    debug_assert!(rem < divisor && divisor <= HALF);
-    let (hi, rem) = ((rem << HALF_BITS) | (digit >> HALF_BITS)).div_rem(&divisor);
-    let (lo, rem) = ((rem << HALF_BITS) | (digit & HALF)).div_rem(&divisor);
-    ((hi << HALF_BITS) | lo, rem)
-}
-
 #[inline]
 pub(super) fn div_rem_digit(mut a: BigUint, b: BigDigit) -> (BigUint, BigDigit) {
     if b == 0 {
         panic!("attempt to divide by zero")
     }
 
-    let mut rem = 0;
-
-    if !FAST_DIV_WIDE && b <= big_digit::HALF {
-        for d in a.data.iter_mut().rev() {
-            let (q, r) = div_half(rem, *d, b);
-            *d = q;
-            rem = r;
-        }
-    } else {
-        for d in a.data.iter_mut().rev() {
-            let (q, r) = div_wide(rem, *d, b);
-            *d = q;
-            rem = r;
-        }
-    }
-
+    let rem = __div_rem_digit(&mut a.data, b);
     (a.normalized(), rem)
 }
 
@@ -112,50 +27,7 @@ fn rem_digit(a: &BigUint, b: BigDigit) -> BigDigit {
         panic!("attempt to divide by zero")
     }
 
-    let mut rem = 0;
-
-    if !FAST_DIV_WIDE && b <= big_digit::HALF {
-        for &digit in a.data.iter().rev() {
-            let (_, r) = div_half(rem, digit, b);
-            rem = r;
-        }
-    } else {
-        for &digit in a.data.iter().rev() {
-            let (_, r) = div_wide(rem, digit, b);
-            rem = r;
-        }
-    }
-
-    rem
-}
-
-/// Subtract a multiple.
-/// a -= b * c
-/// Returns a borrow (if a < b then borrow > 0).
-fn sub_mul_digit_same_len(a: &mut [BigDigit], b: &[BigDigit], c: BigDigit) -> BigDigit {
-    debug_assert!(a.len() == b.len());
-
-    // carry is between -big_digit::MAX and 0, so to avoid overflow we store
-    // offset_carry = carry + big_digit::MAX
-    let mut offset_carry = big_digit::MAX;
-
-    for (x, y) in a.iter_mut().zip(b) {
-        // We want to calculate sum = x - y * c + carry.
-        // sum >= -(big_digit::MAX * big_digit::MAX) - big_digit::MAX
-        // sum <= big_digit::MAX
-        // Offsetting sum by (big_digit::MAX << big_digit::BITS) puts it in DoubleBigDigit range.
-        let offset_sum = big_digit::to_doublebigdigit(big_digit::MAX, *x)
-            - big_digit::MAX as DoubleBigDigit
-            + offset_carry as DoubleBigDigit
-            - *y as DoubleBigDigit * c as DoubleBigDigit;
-
-        let (new_offset_carry, new_x) = big_digit::from_doublebigdigit(offset_sum);
-        offset_carry = new_offset_carry;
-        *x = new_x;
-    }
-
-    // Return the borrow.
-    big_digit::MAX - offset_carry
+    __rem_digit(&a.data, b)
 }
 
 fn div_rem(mut u: BigUint, mut d: BigUint) -> (BigUint, BigUint) {
@@ -167,7 +39,7 @@ fn div_rem(mut u: BigUint, mut d: BigUint) -> (BigUint, BigUint) {
     }
 
     if d.data.len() == 1 {
-        if d.data == [1] {
+        if *d.data == [1] {
             return (u, BigUint::ZERO);
         }
         let (div, rem) = div_rem_digit(u, d.data[0]);
@@ -214,7 +86,7 @@ pub(super) fn div_rem_ref(u: &BigUint, d: &BigUint) -> (BigUint, BigUint) {
     }
 
     if d.data.len() == 1 {
-        if d.data == [1] {
+        if *d.data == [1] {
             return (u.clone(), BigUint::ZERO);
         }
 
@@ -247,99 +119,72 @@ pub(super) fn div_rem_ref(u: &BigUint, d: &BigUint) -> (BigUint, BigUint) {
     }
 }
 
-/// An implementation of the base division algorithm.
-/// Knuth, TAOCP vol 2 section 4.3.1, algorithm D, with an improvement from exercises 19-21.
-fn div_rem_core(mut a: BigUint, b: &[BigDigit]) -> (BigUint, BigUint) {
-    debug_assert!(a.data.len() >= b.len() && b.len() > 1);
-    debug_assert!(b.last().unwrap().leading_zeros() == 0);
-
-    // The algorithm works by incrementally calculating "guesses", q0, for the next digit of the
-    // quotient. Once we have any number q0 such that (q0 << j) * b <= a, we can set
-    //
-    //     q += q0 << j
-    //     a -= (q0 << j) * b
-    //
-    // and then iterate until a < b. Then, (q, a) will be our desired quotient and remainder.
-    //
-    // q0, our guess, is calculated by dividing the last three digits of a by the last two digits of
-    // b - this will give us a guess that is close to the actual quotient, but is possibly greater.
-    // It can only be greater by 1 and only in rare cases, with probability at most
-    // 2^-(big_digit::BITS-1) for random a, see TAOCP 4.3.1 exercise 21.
-    //
-    // If the quotient turns out to be too large, we adjust it by 1:
-    // q -= 1 << j
-    // a += b << j
-
-    // a0 stores an additional extra most significant digit of the dividend, not stored in a.
-    let mut a0 = 0;
-
-    // [b1, b0] are the two most significant digits of the divisor. They never change.
-    let b0 = b[b.len() - 1];
-    let b1 = b[b.len() - 2];
-
-    let q_len = a.data.len() - b.len() + 1;
-    let mut q = BigUint {
-        data: vec![0; q_len],
-    };
-
-    for j in (0..q_len).rev() {
-        debug_assert!(a.data.len() == b.len() + j);
+pub(super) fn div_rem_into(q: &mut BigUint, r: &mut BigUint, u: &BigUint, d: &BigUint) {
+    if d.is_zero() {
+        panic!("attempt to divide by zero")
+    }
+    if u.is_zero() {
+        q.set_zero();
+        r.set_zero();
+        return;
+    }
 
-        let a1 = *a.data.last().unwrap();
-        let a2 = a.data[a.data.len() - 2];
+    if d.data.len() == 1 {
+        q.clone_from(u);
+        let (div, rem) = div_rem_digit(mem::replace(q, BigUint::ZERO), d.data[0]);
+        *q = div;
+        r.set_zero();
+        *r += rem;
+        return;
+    }
 
-        // The first q0 estimate is [a1,a0] / b0. It will never be too small, it may be too large
-        // by at most 2.
-        let (mut q0, mut r) = if a0 < b0 {
-            let (q0, r) = div_wide(a0, a1, b0);
-            (q0, r as DoubleBigDigit)
-        } else {
-            debug_assert!(a0 == b0);
-            // Avoid overflowing q0, we know the quotient fits in BigDigit.
-            // [a1,a0] = b0 * (1<<BITS - 1) + (a0 + a1)
-            (big_digit::MAX, a0 as DoubleBigDigit + a1 as DoubleBigDigit)
-        };
-
-        // r = [a1,a0] - q0 * b0
-        //
-        // Now we want to compute a more precise estimate [a2,a1,a0] / [b1,b0] which can only be
-        // less or equal to the current q0.
-        //
-        // q0 is too large if:
-        // [a2,a1,a0] < q0 * [b1,b0]
-        // (r << BITS) + a2 < q0 * b1
-        while r <= big_digit::MAX as DoubleBigDigit
-            && big_digit::to_doublebigdigit(r as BigDigit, a2)
-                < q0 as DoubleBigDigit * b1 as DoubleBigDigit
-        {
-            q0 -= 1;
-            r += b0 as DoubleBigDigit;
+    match u.cmp(d) {
+        Less => {
+            q.set_zero();
+            r.clone_from(u);
+            return;
         }
-
-        // q0 is now either the correct quotient digit, or in rare cases 1 too large.
-        // Subtract (q0 << j) from a. This may overflow, in which case we will have to correct.
-
-        let mut borrow = sub_mul_digit_same_len(&mut a.data[j..], b, q0);
-        if borrow > a0 {
-            // q0 is too large. We need to add back one multiple of b.
-            q0 -= 1;
-            borrow -= __add2(&mut a.data[j..], b);
+        Equal => {
+            q.set_one();
+            r.set_zero();
+            return;
         }
-        // The top digit of a, stored in a0, has now been zeroed.
-        debug_assert!(borrow == a0);
+        Greater => {} // Do nothing
+    }
 
-        q.data[j] = q0;
+    // Same normalization as `div_rem`, but the remainder is shifted in place.
+    let shift = d.data.last().unwrap().leading_zeros() as usize;
 
-        // Pop off the next top digit of a.
-        a0 = a.data.pop().unwrap();
+    r.clone_from(u);
+    if shift == 0 {
+        div_rem_core_into(q, r, &d.data);
+    } else {
+        *r <<= shift;
+        div_rem_core_into(q, r, &(d << shift).data);
+        shr_bits(&mut r.data, shift as u8);
+        r.normalize();
     }
+}
 
-    a.data.push(a0);
+fn div_rem_core(mut a: BigUint, b: &[BigDigit]) -> (BigUint, BigUint) {
+    let mut q = BigUint::ZERO;
+    div_rem_core_into(&mut q, &mut a, b);
+    (q, a.normalized())
+}
+
+/// Divides `a` by the normalized divisor `b` with [`__div_rem`], leaving the remainder in `a`.
+///
+/// Neither `q` nor `a` gives up its allocation, so the caller decides whether to shrink them.
+fn div_rem_core_into(q: &mut BigUint, a: &mut BigUint, b: &[BigDigit]) {
+    let q_len = a.data.len() - b.len() + 1;
+    q.data.clear();
+    q.data.resize(q_len, 0);
+
+    __div_rem(0, &mut a.data, b, &mut q.data);
     a.normalize();
+    q.normalize();
 
     debug_assert_eq!(cmp_slice(&a.data, b), Less);
-
-    (q.normalized(), a)
 }
 
 forward_val_ref_binop!(impl Div for BigUint, div);
@@ -390,7 +235,8 @@ impl Div<u32> for BigUint {
 impl DivAssign<u32> for BigUint {
     #[inline]
     fn div_assign(&mut self, other: u32) {
-        *self = &*self / other;
+        let temp = mem::replace(self, Self::ZERO);
+        *self = temp / other;
     }
 }
 
@@ -1,3 +1,5 @@
+use crate::big_digit::{self, BigDigit};
+
 use core::iter::FusedIterator;
 
 cfg_digit!(
@@ -276,6 +278,88 @@ impl ExactSizeIterator for U64Digits<'_> {
 
 impl FusedIterator for U64Digits<'_> {}
 
+/// An iterator of the positions of the one bits in a `BigUint`, in increasing order.
+pub struct Ones<'a> {
+    data: &'a [BigDigit],
+    index: usize,
+    current: BigDigit,
+}
+
+impl<'a> Ones<'a> {
+    #[inline]
+    pub(super) fn new(data: &'a [BigDigit]) -> Self {
+        let current = data.first().copied().unwrap_or(0);
+        Ones {
+            data,
+            index: 0,
+            current,
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.data.get(self.index)?;
+        }
+        let bit = self.current.trailing_zeros();
+        self.current &= self.current - 1;
+        Some(self.index as u64 * u64::from(big_digit::BITS) + u64::from(bit))
+    }
+}
+
+impl FusedIterator for Ones<'_> {}
+
+/// An iterator of the positions of the zero bits in a `BigUint` below its most significant one
+/// bit, in increasing order.
+pub struct Zeros<'a> {
+    data: &'a [BigDigit],
+    index: usize,
+    current: BigDigit,
+}
+
+impl<'a> Zeros<'a> {
+    #[inline]
+    pub(super) fn new(data: &'a [BigDigit]) -> Self {
+        let current = Self::zeros_of(data, 0).unwrap_or(0);
+        Zeros {
+            data,
+            index: 0,
+            current,
+        }
+    }
+
+    /// Returns the zero bits of the digit at `index`, excluding the leading zeros of the top digit.
+    fn zeros_of(data: &[BigDigit], index: usize) -> Option<BigDigit> {
+        let digit = *data.get(index)?;
+        if index + 1 < data.len() {
+            Some(!digit)
+        } else {
+            // The top digit is nonzero, so this shift is always less than `BITS`.
+            Some(!digit & (BigDigit::MAX >> digit.leading_zeros()))
+        }
+    }
+}
+
+impl Iterator for Zeros<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = Self::zeros_of(self.data, self.index)?;
+        }
+        let bit = self.current.trailing_zeros();
+        self.current &= self.current - 1;
+        Some(self.index as u64 * u64::from(big_digit::BITS) + u64::from(bit))
+    }
+}
+
+impl FusedIterator for Zeros<'_> {}
+
 #[test]
 fn test_iter_u32_digits() {
     let n = super::BigUint::from(5u8);
@@ -1,30 +1,18 @@
-use super::addition::{__add2, add2};
+use super::addition::add2;
 use super::subtraction::sub2;
-use super::{biguint_from_vec, cmp_slice, BigUint, IntDigits};
+use super::{biguint_from_vec, cmp_slice, BigUint, DigitVec, IntDigits};
 
 use crate::big_digit::{self, BigDigit, DoubleBigDigit};
+use crate::digits::__mac_digit;
 use crate::Sign::{self, Minus, NoSign, Plus};
-use crate::{BigInt, UsizePromotion};
+use crate::{AllocBigIntError, BigInt, UsizePromotion};
 
 use core::cmp::Ordering;
+use core::convert::Infallible;
 use core::iter::Product;
 use core::ops::{Mul, MulAssign};
 use num_traits::{CheckedMul, FromPrimitive, One, Zero};
 
-#[inline]
-pub(super) fn mac_with_carry(
-    a: BigDigit,
-    b: BigDigit,
-    c: BigDigit,
-    acc: &mut DoubleBigDigit,
-) -> BigDigit {
-    *acc += DoubleBigDigit::from(a);
-    *acc += DoubleBigDigit::from(b) * DoubleBigDigit::from(c);
-    let lo = *acc as BigDigit;
-    *acc >>= big_digit::BITS;
-    lo
-}
-
 #[inline]
 fn mul_with_carry(a: BigDigit, b: BigDigit, acc: &mut DoubleBigDigit) -> BigDigit {
     *acc += DoubleBigDigit::from(a) * DoubleBigDigit::from(b);
@@ -36,42 +24,45 @@ fn mul_with_carry(a: BigDigit, b: BigDigit, acc: &mut DoubleBigDigit) -> BigDigi
 /// Three argument multiply accumulate:
 /// acc += b * c
 fn mac_digit(acc: &mut [BigDigit], b: &[BigDigit], c: BigDigit) {
-    if c == 0 {
-        return;
-    }
-
-    let mut carry = 0;
-    let (a_lo, a_hi) = acc.split_at_mut(b.len());
-
-    for (a, &b) in a_lo.iter_mut().zip(b) {
-        *a = mac_with_carry(*a, b, c, &mut carry);
-    }
-
-    let (carry_hi, carry_lo) = big_digit::from_doublebigdigit(carry);
-
-    let final_carry = if carry_hi == 0 {
-        __add2(a_hi, &[carry_lo])
-    } else {
-        __add2(a_hi, &[carry_hi, carry_lo])
-    };
+    let final_carry = __mac_digit(acc, b, c);
     assert_eq!(final_carry, 0, "carry overflow during multiplication!");
 }
 
-fn bigint_from_slice(slice: &[BigDigit]) -> BigInt {
-    BigInt::from(biguint_from_vec(slice.to_vec()))
+/// Creates an empty [`DigitVec`] with room for at least the given number of digits.
+///
+/// [`mac3`] gets all of its temporaries from one of these, so that [`try_mul`] can report an
+/// allocation failure instead of aborting.
+type Alloc<E> = fn(usize) -> Result<DigitVec, E>;
+
+fn alloc_digits(capacity: usize) -> Result<DigitVec, Infallible> {
+    Ok(DigitVec::with_capacity(capacity))
 }
 
 /// Three argument multiply accumulate:
 /// acc += b * c
+fn mac3(acc: &mut [BigDigit], b: &[BigDigit], c: &[BigDigit]) {
+    match try_mac3(acc, b, c, alloc_digits) {
+        Ok(()) => {}
+        Err(never) => match never {},
+    }
+}
+
+/// Three argument multiply accumulate, with temporaries from `alloc`:
+/// acc += b * c
 #[allow(clippy::many_single_char_names)]
-fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
+fn try_mac3<E>(
+    mut acc: &mut [BigDigit],
+    mut b: &[BigDigit],
+    mut c: &[BigDigit],
+    alloc: Alloc<E>,
+) -> Result<(), E> {
     // Least-significant zeros have no effect on the output.
     if let Some(&0) = b.first() {
         if let Some(nz) = b.iter().position(|&d| d != 0) {
             b = &b[nz..];
             acc = &mut acc[nz..];
         } else {
-            return;
+            return Ok(());
         }
     }
     if let Some(&0) = c.first() {
@@ -79,7 +70,7 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
             c = &c[nz..];
             acc = &mut acc[nz..];
         } else {
-            return;
+            return Ok(());
         }
     }
 
@@ -160,8 +151,8 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
         let (low2, high2) = y.split_at(m2);
 
         // (x * high2) * NBASE ^ m2 + z0
-        mac3(acc, x, low2);
-        mac3(&mut acc[m2..], x, high2);
+        try_mac3(acc, x, low2, alloc)?;
+        try_mac3(&mut acc[m2..], x, high2, alloc)?;
     } else if x.len() <= 256 {
         // Karatsuba multiplication:
         //
@@ -233,10 +224,11 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
         // We reuse the same BigUint for all the intermediate multiplies and have to size p
         // appropriately here: x1.len() >= x0.len and y1.len() >= y0.len():
         let len = x1.len() + y1.len() + 1;
-        let mut p = BigUint { data: vec![0; len] };
+        let mut p = BigUint { data: alloc(len)? };
+        p.data.resize(len, 0);
 
         // p2 = x1 * y1
-        mac3(&mut p.data, x1, y1);
+        try_mac3(&mut p.data, x1, y1, alloc)?;
 
         // Not required, but the adds go faster if we drop any unneeded 0s from the end:
         p.normalize();
@@ -249,7 +241,7 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
         p.data.resize(len, 0);
 
         // p0 = x0 * y0
-        mac3(&mut p.data, x0, y0);
+        try_mac3(&mut p.data, x0, y0, alloc)?;
         p.normalize();
 
         add2(acc, &p.data);
@@ -257,21 +249,21 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
 
         // p1 = (x1 - x0) * (y1 - y0)
         // We do this one last, since it may be negative and acc can't ever be negative:
-        let (j0_sign, j0) = sub_sign(x1, x0);
-        let (j1_sign, j1) = sub_sign(y1, y0);
+        let (j0_sign, j0) = sub_sign(x1, x0, alloc)?;
+        let (j1_sign, j1) = sub_sign(y1, y0, alloc)?;
 
         match j0_sign * j1_sign {
             Plus => {
                 p.data.truncate(0);
                 p.data.resize(len, 0);
 
-                mac3(&mut p.data, &j0.data, &j1.data);
+                try_mac3(&mut p.data, &j0.data, &j1.data, alloc)?;
                 p.normalize();
 
                 sub2(&mut acc[b..], &p.data);
             }
             Minus => {
-                mac3(&mut acc[b..], &j0.data, &j1.data);
+                try_mac3(&mut acc[b..], &j0.data, &j1.data, alloc)?;
             }
             NoSign => (),
         }
@@ -292,19 +284,39 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
         let y0_len = i;
         let y1_len = Ord::min(y.len() - y0_len, i);
 
+        // All the temporaries are allocated here with enough room for the values they will
+        // hold, so the arithmetic below updates them in place and never has to grow a buffer.
+        // The parts of x and y have at most i digits, and the sums of parts at most i + 1.
+        let scratch = |sign: Sign, digits: &[BigDigit], capacity: usize| -> Result<BigInt, E> {
+            let mut data = alloc(capacity)?;
+            data.extend_from_slice(digits);
+            Ok(BigInt::from_biguint(sign, biguint_from_vec(data)))
+        };
+        // The products have at most 2 * i + 1 digits, and so do the combinations of them.
+        let product_len = 2 * i + 4;
+        let mul = |a: &BigInt, b: &BigInt| -> Result<BigInt, E> {
+            let mut data = alloc(product_len)?;
+            data.resize(a.digits().len() + b.digits().len() + 1, 0);
+            try_mac3(&mut data, a.digits(), b.digits(), alloc)?;
+            Ok(BigInt::from_biguint(
+                a.sign() * b.sign(),
+                biguint_from_vec(data),
+            ))
+        };
+
         // Break x and y into three parts, representating an order two polynomial.
         // t is chosen to be the size of a digit so we can use faster shifts
         // in place of multiplications.
         //
         // x(t) = x2*t^2 + x1*t + x0
-        let x0 = bigint_from_slice(&x[..x0_len]);
-        let x1 = bigint_from_slice(&x[x0_len..x0_len + x1_len]);
-        let x2 = bigint_from_slice(&x[x0_len + x1_len..]);
+        let x0 = scratch(Plus, &x[..x0_len], x0_len)?;
+        let x1 = scratch(Plus, &x[x0_len..x0_len + x1_len], x1_len)?;
+        let x2 = scratch(Plus, &x[x0_len + x1_len..], x.len() - x0_len - x1_len)?;
 
         // y(t) = y2*t^2 + y1*t + y0
-        let y0 = bigint_from_slice(&y[..y0_len]);
-        let y1 = bigint_from_slice(&y[y0_len..y0_len + y1_len]);
-        let y2 = bigint_from_slice(&y[y0_len + y1_len..]);
+        let y0 = scratch(Plus, &y[..y0_len], y0_len)?;
+        let y1 = scratch(Plus, &y[y0_len..y0_len + y1_len], y1_len)?;
+        let y2 = scratch(Plus, &y[y0_len + y1_len..], y.len() - y0_len - y1_len)?;
 
         // Let w(t) = x(t) * y(t)
         //
@@ -330,32 +342,44 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
         // let d = w(-2)  = (4*x2 - 2*x1 + x0) * (4*y2 - 2*y1 + y0)
         // let e = w(inf) = x2 * y2 as t -> inf
 
-        // x0 + x2, avoiding temporaries
-        let p = &x0 + &x2;
+        // x0 + x2
+        let mut p = scratch(x0.sign(), x0.digits(), i + 3)?;
+        p += &x2;
 
-        // y0 + y2, avoiding temporaries
-        let q = &y0 + &y2;
+        // y0 + y2
+        let mut q = scratch(y0.sign(), y0.digits(), i + 3)?;
+        q += &y2;
 
-        // x2 - x1 + x0, avoiding temporaries
-        let p2 = &p - &x1;
+        // x2 - x1 + x0
+        let mut p2 = scratch(p.sign(), p.digits(), i + 3)?;
+        p2 -= &x1;
 
-        // y2 - y1 + y0, avoiding temporaries
-        let q2 = &q - &y1;
+        // y2 - y1 + y0
+        let mut q2 = scratch(q.sign(), q.digits(), i + 3)?;
+        q2 -= &y1;
 
         // w(0)
-        let r0 = &x0 * &y0;
+        let r0 = mul(&x0, &y0)?;
 
         // w(inf)
-        let r4 = &x2 * &y2;
+        let r4 = mul(&x2, &y2)?;
 
         // w(1)
-        let r1 = (p + x1) * (q + y1);
+        p += &x1;
+        q += &y1;
+        let r1 = mul(&p, &q)?;
 
         // w(-1)
-        let r2 = &p2 * &q2;
+        let r2 = mul(&p2, &q2)?;
 
         // w(-2)
-        let r3 = ((p2 + x2) * 2 - x0) * ((q2 + y2) * 2 - y0);
+        p2 += &x2;
+        p2 <<= 1;
+        p2 -= &x0;
+        q2 += &y2;
+        q2 <<= 1;
+        q2 -= &y0;
+        let r3 = mul(&p2, &q2)?;
 
         // Evaluating these points gives us the following system of linear equations.
         //
@@ -376,11 +400,25 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
         //
         // This particular sequence is given by Bodrato and is an interpolation
         // of the above equations.
-        let mut comp3: BigInt = (r3 - &r1) / 3u32;
-        let mut comp1: BigInt = (r1 - &r2) >> 1;
-        let mut comp2: BigInt = r2 - &r0;
-        comp3 = ((&comp2 - comp3) >> 1) + (&r4 << 1);
-        comp2 += &comp1 - &r4;
+        //
+        // Every step works in place on the buffers of the products.
+        let mut comp3 = r3;
+        comp3 -= &r1;
+        comp3 /= 3u32;
+        let mut comp1 = r1;
+        comp1 -= &r2;
+        comp1 >>= 1;
+        let mut comp2 = r2;
+        comp2 -= &r0;
+        // comp3 = ((comp2 - comp3) >> 1) + (r4 << 1)
+        comp3 = -comp3;
+        comp3 += &comp2;
+        comp3 >>= 1;
+        comp3 += &r4;
+        comp3 += &r4;
+        // comp2 += comp1 - r4
+        comp2 += &comp1;
+        comp2 -= &r4;
         comp1 -= &comp3;
 
         // Recomposition. The coefficients of the polynomial are now known.
@@ -405,16 +443,85 @@ fn mac3(mut acc: &mut [BigDigit], mut b: &[BigDigit], mut c: &[BigDigit]) {
             }
         }
     }
+    Ok(())
 }
 
 fn mul3(x: &[BigDigit], y: &[BigDigit]) -> BigUint {
     let len = x.len() + y.len() + 1;
-    let mut prod = BigUint { data: vec![0; len] };
+    let mut prod = BigUint {
+        data: DigitVec::zeroed(len),
+    };
 
     mac3(&mut prod.data, x, y);
     prod.normalized()
 }
 
+pub(super) fn try_mul(x: &BigUint, y: &BigUint) -> Result<BigUint, AllocBigIntError> {
+    if x.is_zero() || y.is_zero() {
+        return Ok(BigUint::ZERO);
+    }
+    let len = x.data.len() + y.data.len() + 1;
+    let mut data = DigitVec::try_with_capacity(len)?;
+    data.resize(len, 0);
+    try_mac3(&mut data, &x.data, &y.data, DigitVec::try_with_capacity)?;
+    Ok(biguint_from_vec(data))
+}
+
+pub(super) fn mul_into(out: &mut BigUint, x: &[BigDigit], y: &[BigDigit]) {
+    out.data.clear();
+    if x.is_empty() || y.is_empty() {
+        return;
+    }
+    out.data.resize(x.len() + y.len() + 1, 0);
+    mac3(&mut out.data, x, y);
+    out.normalize();
+}
+
+/// Computes `acc += b * c` in place, without materializing the product.
+pub(crate) fn add_mul(acc: &mut BigUint, b: &[BigDigit], c: &[BigDigit]) {
+    if b.is_empty() || c.is_empty() {
+        return;
+    }
+    let len = Ord::max(acc.data.len(), b.len() + c.len()) + 1;
+    acc.data.resize(len, 0);
+    mac3(&mut acc.data, b, c);
+    acc.normalize();
+}
+
+/// Computes `|acc - b * c|` in place, without materializing the product, and returns the sign of
+/// `acc - b * c`.
+pub(crate) fn sub_mul(acc: &mut BigUint, b: &[BigDigit], c: &[BigDigit]) -> Sign {
+    if !b.is_empty() && !c.is_empty() {
+        // acc can't go negative, so accumulate onto its ones' complement instead:
+        //
+        //     !acc + b * c = (2^n - 1) - (acc - b * c)
+        //
+        // where `n` covers both acc and the product, with one more digit for the carry. That top
+        // digit is set exactly when the product is larger, and then the low digits hold
+        // `b * c - acc - 1`. Otherwise, complementing them again yields `acc - b * c`.
+        let len = Ord::max(acc.data.len(), b.len() + c.len()) + 1;
+        acc.data.resize(len + 1, 0);
+        for d in &mut acc.data[..len] {
+            *d = !*d;
+        }
+        mac3(&mut acc.data, b, c);
+        if acc.data.pop() != Some(0) {
+            add2(&mut acc.data, &[1]);
+            acc.normalize();
+            return Minus;
+        }
+        for d in acc.data.iter_mut() {
+            *d = !*d;
+        }
+        acc.normalize();
+    }
+    if acc.is_zero() {
+        NoSign
+    } else {
+        Plus
+    }
+}
+
 fn scalar_mul(a: &mut BigUint, b: BigDigit) {
     match b {
         0 => a.set_zero(),
@@ -435,7 +542,11 @@ fn scalar_mul(a: &mut BigUint, b: BigDigit) {
     }
 }
 
-fn sub_sign(mut a: &[BigDigit], mut b: &[BigDigit]) -> (Sign, BigUint) {
+fn sub_sign<E>(
+    mut a: &[BigDigit],
+    mut b: &[BigDigit],
+    alloc: Alloc<E>,
+) -> Result<(Sign, BigUint), E> {
     // Normalize:
     if let Some(&0) = a.last() {
         a = &a[..a.iter().rposition(|&x| x != 0).map_or(0, |i| i + 1)];
@@ -444,19 +555,15 @@ fn sub_sign(mut a: &[BigDigit], mut b: &[BigDigit]) -> (Sign, BigUint) {
         b = &b[..b.iter().rposition(|&x| x != 0).map_or(0, |i| i + 1)];
     }
 
-    match cmp_slice(a, b) {
-        Ordering::Greater => {
-            let mut a = a.to_vec();
-            sub2(&mut a, b);
-            (Plus, biguint_from_vec(a))
-        }
-        Ordering::Less => {
-            let mut b = b.to_vec();
-            sub2(&mut b, a);
-            (Minus, biguint_from_vec(b))
-        }
-        Ordering::Equal => (NoSign, BigUint::ZERO),
-    }
+    let (sign, hi, lo) = match cmp_slice(a, b) {
+        Ordering::Greater => (Plus, a, b),
+        Ordering::Less => (Minus, b, a),
+        Ordering::Equal => return Ok((NoSign, BigUint::ZERO)),
+    };
+    let mut diff = alloc(hi.len())?;
+    diff.extend_from_slice(hi);
+    sub2(&mut diff, lo);
+    Ok((sign, biguint_from_vec(diff)))
 }
 
 macro_rules! impl_mul {
@@ -612,7 +719,7 @@ fn test_sub_sign() {
     use num_traits::Num;
 
     fn sub_sign_i(a: &[BigDigit], b: &[BigDigit]) -> BigInt {
-        let (sign, val) = sub_sign(a, b);
+        let (sign, val) = sub_sign(a, b, alloc_digits).unwrap();
         BigInt::from_biguint(sign, val)
     }
 
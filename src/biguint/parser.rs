@@ -0,0 +1,217 @@
+use super::convert::get_radix_base;
+use super::{biguint_from_vec, BigUint};
+
+use crate::big_digit::{self, BigDigit, DoubleBigDigit};
+use crate::digits::mac_with_carry;
+use crate::ParseBigIntError;
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::io;
+
+/// An incremental parser for a [`BigUint`] whose text arrives in pieces.
+///
+/// The input is the same as for [`from_str_radix`][num_traits::Num::from_str_radix]: an optional
+/// `+` sign, then digits in the given radix, with `_` allowed between digits. Each call to
+/// [`push()`][Self::push] consumes another piece of the input, and [`finish()`][Self::finish]
+/// returns the complete number. The digits are accumulated one big digit's worth at a time, so
+/// the input text is never buffered as a whole.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{BigUint, BigUintParser};
+///
+/// let mut parser = BigUintParser::new(10);
+/// parser.push(b"123_456").unwrap();
+/// parser.push(b"789012345678901234567890").unwrap();
+/// let n = parser.finish().unwrap();
+/// assert_eq!(n.to_string(), "123456789012345678901234567890");
+///
+/// let mut parser = BigUintParser::new(16);
+/// parser.push(b"ff").unwrap();
+/// let err = parser.push(b"fg").unwrap_err();
+/// assert_eq!(err.position(), Some(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BigUintParser {
+    radix: u32,
+    /// The number of digits in a full chunk.
+    power: usize,
+    /// `radix.pow(power)`, for radixes that are not powers of two.
+    base: BigDigit,
+    /// For powers of two, the full chunks in big-endian order. Otherwise, the little-endian
+    /// value of all the full chunks so far.
+    data: Vec<BigDigit>,
+    /// The value of the partial chunk of digits.
+    chunk: BigDigit,
+    chunk_len: usize,
+    /// The number of bytes pushed so far.
+    position: usize,
+    state: State,
+}
+
+#[derive(Clone, Debug)]
+enum State {
+    Start,
+    Sign,
+    Digits,
+    Failed(ParseBigIntError),
+}
+
+impl BigUintParser {
+    /// Creates a parser for digits in the given radix.
+    ///
+    /// Panics if `radix` is not in the range `2...36`.
+    pub fn new(radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "The radix must be within 2...36");
+        let (base, power) = if radix.is_power_of_two() {
+            let bits = radix.trailing_zeros() as usize;
+            (0, big_digit::BITS as usize / bits)
+        } else {
+            get_radix_base(radix)
+        };
+        BigUintParser {
+            radix,
+            power,
+            base,
+            data: Vec::new(),
+            chunk: 0,
+            chunk_len: 0,
+            position: 0,
+            state: State::Start,
+        }
+    }
+
+    /// Parses the next piece of the input.
+    ///
+    /// Once this has returned an error, the parser will keep returning that same error.
+    /// The error's [`position()`][ParseBigIntError::position] counts from the start of the first
+    /// piece.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<(), ParseBigIntError> {
+        if let State::Failed(err) = &self.state {
+            return Err(err.clone());
+        }
+        for (i, &b) in bytes.iter().enumerate() {
+            let d = match b {
+                b'0'..=b'9' => b - b'0',
+                b'a'..=b'z' => b - b'a' + 10,
+                b'A'..=b'Z' => b - b'A' + 10,
+                b'_' => match self.state {
+                    State::Digits => continue,
+                    _ => {
+                        return self.fail(ParseBigIntError::misplaced_underscore(self.position + i))
+                    }
+                },
+                b'+' if matches!(self.state, State::Start) => {
+                    self.state = State::Sign;
+                    continue;
+                }
+                _ => u8::MAX,
+            };
+            if u32::from(d) >= self.radix {
+                return self.fail(ParseBigIntError::invalid(self.position + i));
+            }
+            self.state = State::Digits;
+
+            self.chunk = self.chunk * self.radix as BigDigit + BigDigit::from(d);
+            self.chunk_len += 1;
+            if self.chunk_len == self.power {
+                if self.radix.is_power_of_two() {
+                    self.data.push(self.chunk);
+                } else {
+                    mul_add(&mut self.data, self.base, self.chunk);
+                }
+                self.chunk = 0;
+                self.chunk_len = 0;
+            }
+        }
+        self.position += bytes.len();
+        Ok(())
+    }
+
+    fn fail(&mut self, err: ParseBigIntError) -> Result<(), ParseBigIntError> {
+        self.state = State::Failed(err.clone());
+        Err(err)
+    }
+
+    /// Reads the rest of the input from `reader`, until it reaches the end of the stream.
+    ///
+    /// Parsing errors are returned as [`io::ErrorKind::InvalidData`], wrapping the
+    /// [`ParseBigIntError`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_from<R: io::Read>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut buf = [0u8; 1024];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(n) => self
+                    .push(&buf[..n])
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Completes parsing, returning the number or the first error.
+    pub fn finish(self) -> Result<BigUint, ParseBigIntError> {
+        match self.state {
+            State::Start => return Err(ParseBigIntError::empty()),
+            State::Sign => return Err(ParseBigIntError::empty().after_sign()),
+            State::Failed(err) => return Err(err),
+            State::Digits => {}
+        }
+
+        let mut data = self.data;
+        if !self.radix.is_power_of_two() {
+            if self.chunk_len > 0 {
+                let base = (self.radix as BigDigit).pow(self.chunk_len as u32);
+                mul_add(&mut data, base, self.chunk);
+            }
+            return Ok(biguint_from_vec(data));
+        }
+
+        // Pack the chunks' bits into big digits, starting from the least significant.
+        let bits = self.radix.trailing_zeros() as usize;
+        let mut out = Vec::with_capacity(data.len() + 1);
+        let mut acc = 0;
+        let mut acc_bits = 0;
+        let mut pack = |value: BigDigit, n: usize| {
+            acc |= value << acc_bits;
+            acc_bits += n;
+            if acc_bits >= big_digit::BITS as usize {
+                out.push(acc);
+                acc_bits -= big_digit::BITS as usize;
+                acc = if acc_bits > 0 {
+                    value >> (n - acc_bits)
+                } else {
+                    0
+                };
+            }
+        };
+        if self.chunk_len > 0 {
+            pack(self.chunk, self.chunk_len * bits);
+        }
+        for &chunk in data.iter().rev() {
+            pack(chunk, self.power * bits);
+        }
+        if acc_bits > 0 {
+            out.push(acc);
+        }
+        Ok(biguint_from_vec(out))
+    }
+}
+
+/// `data = data * base + n`, for little-endian `data`.
+fn mul_add(data: &mut Vec<BigDigit>, base: BigDigit, n: BigDigit) {
+    let mut carry = DoubleBigDigit::from(n);
+    for d in data.iter_mut() {
+        *d = mac_with_carry(0, *d, base, &mut carry);
+    }
+    if carry != 0 {
+        data.push(carry as BigDigit);
+    }
+}
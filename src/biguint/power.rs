@@ -1,7 +1,8 @@
 use super::monty::monty_modpow;
-use super::BigUint;
+use super::{BigUint, DigitVec};
 
 use crate::big_digit::{self, BigDigit};
+use crate::AllocBigIntError;
 
 use num_integer::Integer;
 use num_traits::{One, Pow, ToPrimitive, Zero};
@@ -60,6 +61,42 @@ impl Pow<BigUint> for &BigUint {
     }
 }
 
+pub(super) fn try_pow(base: &BigUint, mut exp: u32) -> Result<BigUint, AllocBigIntError> {
+    if exp == 0 || base.is_one() {
+        return Ok(BigUint::one());
+    }
+    if base.is_zero() {
+        return Ok(BigUint::ZERO);
+    }
+    // Fail fast if the result can't possibly be allocated, before doing any of the work.
+    let bits = base.bits().checked_mul(u64::from(exp));
+    if bits.map_or(true, |bits| bits / 8 > isize::MAX as u64) {
+        return Err(AllocBigIntError::new());
+    }
+
+    let mut base = try_clone(base)?;
+    while exp & 1 == 0 {
+        base = base.try_mul(&base)?;
+        exp >>= 1;
+    }
+
+    let mut acc = try_clone(&base)?;
+    while exp > 1 {
+        exp >>= 1;
+        base = base.try_mul(&base)?;
+        if exp & 1 == 1 {
+            acc = acc.try_mul(&base)?;
+        }
+    }
+    Ok(acc)
+}
+
+fn try_clone(x: &BigUint) -> Result<BigUint, AllocBigIntError> {
+    let mut data = DigitVec::try_with_capacity(x.data.len())?;
+    data.extend_from_slice(&x.data);
+    Ok(BigUint { data })
+}
+
 macro_rules! pow_impl {
     ($T:ty) => {
         impl Pow<$T> for BigUint {
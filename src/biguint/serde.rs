@@ -4,8 +4,11 @@
 use super::{biguint_from_vec, BigUint};
 
 use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
 use core::{cmp, fmt, mem};
-use serde::de::{SeqAccess, Visitor};
+use num_integer::Integer;
+use serde::de::{self, DeserializeSeed, SeqAccess, Unexpected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // `cautious` is based on the function of the same name in `serde`, but specialized to `u32`:
@@ -65,17 +68,112 @@ impl<'de> Deserialize<'de> for BigUint {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_seq(U32Visitor)
+        deserializer.deserialize_seq(U32Visitor { max_bits: u64::MAX })
     }
 }
 
-struct U32Visitor;
+/// A [`DeserializeSeed`] for a [`BigUint`] or [`BigInt`][crate::BigInt] with a limit on its bit
+/// length, for deserializing untrusted input.
+///
+/// Deserialization fails as soon as the input holds more digits than the limit allows, without
+/// buffering the rest. A [`BigInt`][crate::BigInt] is limited by the bits of its magnitude.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{BigUint, MaxBits};
+/// use serde::de::value::{Error, SeqDeserializer};
+/// use serde::de::DeserializeSeed;
+///
+/// let digits = || SeqDeserializer::<_, Error>::new([1u32, 2].into_iter());
+/// let n = MaxBits::<BigUint>::new(64).deserialize(digits()).unwrap();
+/// assert_eq!(n, BigUint::from(0x2_0000_0001u64));
+/// assert!(MaxBits::<BigUint>::new(33).deserialize(digits()).is_err());
+/// ```
+pub struct MaxBits<T> {
+    max_bits: u64,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> MaxBits<T> {
+    /// Creates a seed that accepts values of at most `max_bits` bits.
+    pub fn new(max_bits: u64) -> Self {
+        MaxBits {
+            max_bits,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn max_bits(&self) -> u64 {
+        self.max_bits
+    }
+}
+
+impl<T> Clone for MaxBits<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MaxBits<T> {}
+
+impl<T> fmt::Debug for MaxBits<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaxBits")
+            .field("max_bits", &self.max_bits)
+            .finish()
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for MaxBits<BigUint> {
+    type Value = BigUint;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<BigUint, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(U32Visitor {
+            max_bits: self.max_bits,
+        })
+    }
+}
+
+struct U32Visitor {
+    max_bits: u64,
+}
+
+impl U32Visitor {
+    /// Returns the most `u32` values that can make up a value within the limit.
+    fn max_len(&self) -> usize {
+        usize::try_from(Integer::div_ceil(&self.max_bits, &32)).unwrap_or(usize::MAX)
+    }
+
+    fn check<E: de::Error>(&self, len: usize) -> Result<(), E> {
+        if len > self.max_len() {
+            Err(E::invalid_length(len, self))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn finish<E: de::Error>(&self, n: BigUint) -> Result<BigUint, E> {
+        if n.bits() > self.max_bits {
+            Err(E::invalid_value(Unexpected::Seq, self))
+        } else {
+            Ok(n)
+        }
+    }
+}
 
 impl<'de> Visitor<'de> for U32Visitor {
     type Value = BigUint;
 
     fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.write_str("a sequence of unsigned 32-bit numbers")
+        formatter.write_str("a sequence of unsigned 32-bit numbers")?;
+        if self.max_bits != u64::MAX {
+            write!(formatter, " of at most {} bits", self.max_bits)?;
+        }
+        Ok(())
     }
 
     cfg_digit!(
@@ -83,14 +181,15 @@ impl<'de> Visitor<'de> for U32Visitor {
         where
             S: SeqAccess<'de>,
         {
-            let len = cautious(seq.size_hint());
+            let len = cmp::min(cautious(seq.size_hint()), self.max_len());
             let mut data = Vec::with_capacity(len);
 
             while let Some(value) = seq.next_element::<u32>()? {
                 data.push(value);
+                self.check(data.len())?;
             }
 
-            Ok(biguint_from_vec(data))
+            self.finish(biguint_from_vec(data))
         }
 
         fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
@@ -98,24 +197,25 @@ impl<'de> Visitor<'de> for U32Visitor {
             S: SeqAccess<'de>,
         {
             use crate::big_digit::BigDigit;
-            use num_integer::Integer;
 
-            let u32_len = cautious(seq.size_hint());
+            let u32_len = cmp::min(cautious(seq.size_hint()), self.max_len());
             let len = Integer::div_ceil(&u32_len, &2);
             let mut data = Vec::with_capacity(len);
 
             while let Some(lo) = seq.next_element::<u32>()? {
                 let mut value = BigDigit::from(lo);
+                self.check(data.len() * 2 + 1)?;
                 if let Some(hi) = seq.next_element::<u32>()? {
                     value |= BigDigit::from(hi) << 32;
                     data.push(value);
+                    self.check(data.len() * 2)?;
                 } else {
                     data.push(value);
                     break;
                 }
             }
 
-            Ok(biguint_from_vec(data))
+            self.finish(biguint_from_vec(data))
         }
     );
 }
@@ -1,12 +1,13 @@
-use super::{biguint_from_vec, BigUint};
+use super::{biguint_from_vec, BigUint, DigitVec};
 
-use crate::big_digit;
+use crate::big_digit::{self, BigDigit};
+use crate::AllocBigIntError;
 
 use alloc::borrow::Cow;
-use alloc::vec::Vec;
 use core::mem;
 use core::ops::{Shl, ShlAssign, Shr, ShrAssign};
-use num_traits::{PrimInt, Zero};
+use num_integer::Integer;
+use num_traits::{PrimInt, ToPrimitive, Zero};
 
 #[inline]
 fn biguint_shl<T: PrimInt>(n: Cow<'_, BigUint>, shift: T) -> BigUint {
@@ -23,17 +24,32 @@ fn biguint_shl<T: PrimInt>(n: Cow<'_, BigUint>, shift: T) -> BigUint {
 }
 
 fn biguint_shl2(n: Cow<'_, BigUint>, digits: usize, shift: u8) -> BigUint {
-    let mut data = match digits {
-        0 => n.into_owned().data,
-        _ => {
+    let data = match (digits, n) {
+        (0, n) => n.into_owned().data,
+        (_, Cow::Borrowed(n)) => {
             let len = digits.saturating_add(n.data.len() + 1);
-            let mut data = Vec::with_capacity(len);
+            let mut data = DigitVec::with_capacity(len);
             data.resize(digits, 0);
-            data.extend(n.data.iter());
+            data.extend_from_slice(&n.data);
+            data
+        }
+        (_, Cow::Owned(n)) => {
+            // Shift the digits up within the same allocation, which may already be big enough.
+            let mut data = n.data;
+            let len = data.len();
+            data.reserve(digits.saturating_add(1));
+            data.resize(len + digits, 0);
+            data.copy_within(..len, digits);
+            data[..digits].fill(0);
             data
         }
     };
+    shl_bits(data, digits, shift)
+}
 
+/// Shifts the digits from `digits` onward left by `shift` bits, where the lower digits have
+/// already been filled with zeros.
+fn shl_bits(mut data: DigitVec, digits: usize, shift: u8) -> BigUint {
     if shift > 0 {
         let mut carry = 0;
         let carry_shift = big_digit::BITS - shift;
@@ -50,6 +66,26 @@ fn biguint_shl2(n: Cow<'_, BigUint>, digits: usize, shift: u8) -> BigUint {
     biguint_from_vec(data)
 }
 
+pub(super) fn try_shl(n: &BigUint, bits: u64) -> Result<BigUint, AllocBigIntError> {
+    if n.is_zero() {
+        return Ok(BigUint::ZERO);
+    }
+    let total = n
+        .bits()
+        .checked_add(bits)
+        .ok_or_else(AllocBigIntError::new)?;
+    let bits_per_digit = u64::from(big_digit::BITS);
+    // The result has exactly `total` bits, so this capacity covers the carry digit too.
+    let len = Integer::div_ceil(&total, &bits_per_digit)
+        .to_usize()
+        .ok_or_else(AllocBigIntError::new)?;
+    let mut data = DigitVec::try_with_capacity(len)?;
+    let digits = (bits / bits_per_digit) as usize;
+    data.resize(digits, 0);
+    data.extend_from_slice(&n.data);
+    Ok(shl_bits(data, digits, (bits % bits_per_digit) as u8))
+}
+
 #[inline]
 fn biguint_shr<T: PrimInt>(n: Cow<'_, BigUint>, shift: T) -> BigUint {
     if shift < T::zero() {
@@ -71,13 +107,19 @@ fn biguint_shr2(n: Cow<'_, BigUint>, digits: usize, shift: u8) -> BigUint {
         return n;
     }
     let mut data = match n {
-        Cow::Borrowed(n) => n.data[digits..].to_vec(),
+        Cow::Borrowed(n) => DigitVec::from_slice(&n.data[digits..]),
         Cow::Owned(mut n) => {
-            n.data.drain(..digits);
+            n.data.remove_prefix(digits);
             n.data
         }
     };
 
+    shr_bits(&mut data, shift);
+    biguint_from_vec(data)
+}
+
+/// Shifts the digits right by `shift` bits, less than a digit, without normalizing them.
+pub(super) fn shr_bits(data: &mut [BigDigit], shift: u8) {
     if shift > 0 {
         let mut borrow = 0;
         let borrow_shift = big_digit::BITS - shift;
@@ -87,8 +129,6 @@ fn biguint_shr2(n: Cow<'_, BigUint>, digits: usize, shift: u8) -> BigUint {
             borrow = new_borrow;
         }
     }
-
-    biguint_from_vec(data)
 }
 
 macro_rules! impl_shift {
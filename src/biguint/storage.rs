@@ -0,0 +1,501 @@
+//! Digit storage for `BigUint`, which keeps small values inline to avoid heap allocation.
+
+use crate::big_digit::{BigDigit, U128_DIGITS};
+use crate::AllocBigIntError;
+
+use alloc::vec::Vec;
+use core::mem::{self, ManuallyDrop};
+use core::num::NonZeroUsize;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::{hash, slice};
+
+/// The number of digits that are stored inline, enough for any `u128`.
+const INLINE: usize = U128_DIGITS;
+
+/// The tag of static digits, which is out of reach of any heap capacity.
+const STATIC: usize = usize::MAX;
+
+/// A vector of digits which stores up to [`INLINE`] digits without allocating.
+///
+/// This supports the subset of the `Vec` API that the arithmetic needs. Values move to the heap
+/// when they grow past the inline capacity, and move back when shrunk to fit. Constants too large
+/// to be inline borrow static digits instead, which are copied on the first mutation.
+///
+/// To be no bigger than a `Vec` on 64-bit targets, the representation is packed into a tag that
+/// is one more than the inline length or the heap capacity, which can be told apart because heap
+/// capacities are always more than [`INLINE`], or else [`STATIC`]. The tag is never zero, so an
+/// `Option<BigUint>` is no bigger either.
+pub(crate) struct DigitVec {
+    tag: NonZeroUsize,
+    data: Data,
+}
+
+#[derive(Clone, Copy)]
+union Data {
+    inline: [BigDigit; INLINE],
+    /// The pointer and length of heap or static digits.
+    slice: (NonNull<BigDigit>, usize),
+}
+
+/// The representation of a [`DigitVec`], as decoded from its tag.
+#[derive(Clone, Copy)]
+enum Kind {
+    Inline { len: usize },
+    Heap { capacity: usize },
+    Static,
+}
+
+// Safety: heap digits are owned exclusively, like a `Vec`, and static digits are never mutated.
+unsafe impl Send for DigitVec {}
+unsafe impl Sync for DigitVec {}
+
+impl DigitVec {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self::from_inline([0; INLINE], 0)
+    }
+
+    /// Creates an inline vector from the first `len` digits, in a const context.
+    #[inline]
+    pub(crate) const fn from_inline(digits: [BigDigit; INLINE], len: usize) -> Self {
+        assert!(len <= INLINE);
+        DigitVec {
+            tag: tag(len + 1),
+            data: Data { inline: digits },
+        }
+    }
+
+    /// Creates a vector of static digits in a const context, copying them if they fit inline.
+    pub(crate) const fn from_static(slice: &'static [BigDigit]) -> Self {
+        if slice.len() > INLINE {
+            // Safety: a reference is never null. The digits are only ever read through it.
+            let ptr = unsafe { NonNull::new_unchecked(slice.as_ptr() as *mut BigDigit) };
+            return DigitVec {
+                tag: tag(STATIC),
+                data: Data {
+                    slice: (ptr, slice.len()),
+                },
+            };
+        }
+        let mut digits = [0; INLINE];
+        let mut i = 0;
+        while i < slice.len() {
+            digits[i] = slice[i];
+            i += 1;
+        }
+        Self::from_inline(digits, slice.len())
+    }
+
+    /// Takes ownership of the `Vec` allocation, or copies its digits inline if its capacity is
+    /// too small to tell apart from an inline length.
+    fn from_heap(vec: Vec<BigDigit>) -> Self {
+        if vec.capacity() <= INLINE {
+            return Self::from_slice(&vec);
+        }
+        let mut vec = ManuallyDrop::new(vec);
+        // Safety: a `Vec` with non-zero capacity has a non-null pointer to its allocation.
+        let ptr = unsafe { NonNull::new_unchecked(vec.as_mut_ptr()) };
+        DigitVec {
+            tag: tag(vec.capacity() + 1),
+            data: Data {
+                slice: (ptr, vec.len()),
+            },
+        }
+    }
+
+    #[inline]
+    fn kind(&self) -> Kind {
+        match self.tag.get() {
+            STATIC => Kind::Static,
+            tag if tag <= INLINE + 1 => Kind::Inline { len: tag - 1 },
+            tag => Kind::Heap { capacity: tag - 1 },
+        }
+    }
+
+    /// Takes the heap digits out as a `Vec`, leaving the vector empty.
+    fn take_heap(&mut self) -> Vec<BigDigit> {
+        let capacity = match self.kind() {
+            Kind::Heap { capacity } => capacity,
+            _ => unreachable!(),
+        };
+        let this = ManuallyDrop::new(mem::take(self));
+        // Safety: the heap parts came from a `Vec` in `from_heap`, and `this` won't drop them.
+        unsafe {
+            let (ptr, len) = this.data.slice;
+            Vec::from_raw_parts(ptr.as_ptr(), len, capacity)
+        }
+    }
+
+    /// Calls `f` with the digits in a heap `Vec`, moving them there first if they're inline or
+    /// static, with room for `additional` more.
+    fn with_heap<R>(&mut self, additional: usize, f: impl FnOnce(&mut Vec<BigDigit>) -> R) -> R {
+        let mut vec = match self.kind() {
+            Kind::Heap { .. } => self.take_heap(),
+            Kind::Inline { .. } | Kind::Static => {
+                let mut vec = Vec::with_capacity(self.len().saturating_add(additional));
+                vec.extend_from_slice(self);
+                vec
+            }
+        };
+        // If `f` panics, the `Vec` is dropped here and `self` is left empty or as it was.
+        let result = f(&mut vec);
+        *self = Self::from_heap(vec);
+        result
+    }
+
+    #[inline]
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE {
+            Self::new()
+        } else {
+            Self::from_heap(Vec::with_capacity(capacity))
+        }
+    }
+
+    /// Creates an empty vector with room for `capacity` digits, failing instead of aborting if
+    /// the memory can't be allocated.
+    pub(crate) fn try_with_capacity(capacity: usize) -> Result<Self, AllocBigIntError> {
+        if capacity <= INLINE {
+            return Ok(Self::new());
+        }
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(capacity)
+            .map_err(|_| AllocBigIntError::new())?;
+        Ok(Self::from_heap(vec))
+    }
+
+    /// Creates a vector of `len` zero digits.
+    #[inline]
+    pub(crate) fn zeroed(len: usize) -> Self {
+        let mut digits = Self::with_capacity(len);
+        digits.resize(len, 0);
+        digits
+    }
+
+    #[inline]
+    pub(crate) fn from_slice(slice: &[BigDigit]) -> Self {
+        if slice.len() <= INLINE {
+            let mut digits = [0; INLINE];
+            digits[..slice.len()].copy_from_slice(slice);
+            Self::from_inline(digits, slice.len())
+        } else {
+            Self::from_heap(slice.to_vec())
+        }
+    }
+
+    #[inline]
+    pub(crate) fn capacity(&self) -> usize {
+        match self.kind() {
+            Kind::Inline { .. } => INLINE,
+            Kind::Heap { capacity } => capacity,
+            Kind::Static => self.len(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        if self.len().saturating_add(additional) > self.capacity() {
+            self.with_heap(additional, |vec| vec.reserve(additional));
+        }
+    }
+
+    #[inline]
+    pub(crate) fn shrink_to_fit(&mut self) {
+        if let Kind::Heap { .. } = self.kind() {
+            if self.len() <= INLINE {
+                *self = Self::from_slice(self);
+            } else {
+                self.with_heap(0, Vec::shrink_to_fit);
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, digit: BigDigit) {
+        match self.kind() {
+            Kind::Inline { len } if len < INLINE => {
+                // Safety: inline digits are always initialized.
+                unsafe { self.data.inline[len] = digit };
+                self.tag = tag(len + 2);
+            }
+            Kind::Heap { capacity } if self.len() < capacity => {
+                // Safety: there's room for the digit in the heap allocation.
+                unsafe {
+                    let (ptr, len) = &mut self.data.slice;
+                    ptr.as_ptr().add(*len).write(digit);
+                    *len += 1;
+                }
+            }
+            _ => self.with_heap(1, |vec| vec.push(digit)),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<BigDigit> {
+        let (&last, rest) = self.split_last()?;
+        self.truncate(rest.len());
+        Some(last)
+    }
+
+    #[inline]
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        match self.kind() {
+            Kind::Inline { .. } => self.tag = tag(new_len + 1),
+            // The digits are `Copy`, so they're simply forgotten, and static digits are resliced.
+            Kind::Heap { .. } | Kind::Static => self.data.slice.1 = new_len,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    pub(crate) fn resize(&mut self, new_len: usize, value: BigDigit) {
+        let len = self.len();
+        match self.kind() {
+            _ if new_len <= len => self.truncate(new_len),
+            Kind::Inline { .. } if new_len <= INLINE => {
+                // Safety: inline digits are always initialized.
+                unsafe { self.data.inline[len..new_len].fill(value) };
+                self.tag = tag(new_len + 1);
+            }
+            _ => self.with_heap(new_len - len, |vec| vec.resize(new_len, value)),
+        }
+    }
+
+    pub(crate) fn extend_from_slice(&mut self, slice: &[BigDigit]) {
+        match self.kind() {
+            Kind::Inline { len } if len + slice.len() <= INLINE => {
+                // Safety: inline digits are always initialized.
+                unsafe { self.data.inline[len..len + slice.len()].copy_from_slice(slice) };
+                self.tag = tag(len + slice.len() + 1);
+            }
+            _ => self.with_heap(slice.len(), |vec| vec.extend_from_slice(slice)),
+        }
+    }
+
+    /// Removes the first `count` digits, shifting the rest down.
+    pub(crate) fn remove_prefix(&mut self, count: usize) {
+        let len = self.len();
+        assert!(count <= len, "prefix is out of bounds");
+        match self.kind() {
+            Kind::Inline { .. } => {
+                // Safety: inline digits are always initialized.
+                unsafe { self.data.inline.copy_within(count..len, 0) };
+                self.tag = tag(len - count + 1);
+            }
+            Kind::Heap { .. } => {
+                self.with_heap(0, |vec| vec.drain(..count).for_each(drop));
+            }
+            // Safety: the count is in bounds, so this reslices the static digits.
+            Kind::Static => unsafe {
+                let (ptr, len) = &mut self.data.slice;
+                *ptr = NonNull::new_unchecked(ptr.as_ptr().add(count));
+                *len -= count;
+            },
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[BigDigit] {
+        self
+    }
+}
+
+/// Returns a tag, which must not be zero.
+#[inline]
+const fn tag(value: usize) -> NonZeroUsize {
+    match NonZeroUsize::new(value) {
+        Some(tag) => tag,
+        None => panic!("digit vector tag is zero"),
+    }
+}
+
+impl Drop for DigitVec {
+    #[inline]
+    fn drop(&mut self) {
+        if let Kind::Heap { .. } = self.kind() {
+            drop(self.take_heap());
+        }
+    }
+}
+
+impl Default for DigitVec {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Cloning allocates only if the value doesn't fit inline, regardless of the source capacity.
+// Static digits are shared rather than copied.
+impl Clone for DigitVec {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self.kind() {
+            Kind::Static => DigitVec {
+                tag: self.tag,
+                data: self.data,
+            },
+            _ => Self::from_slice(self),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        match self.kind() {
+            Kind::Heap { .. } => self.with_heap(0, |vec| {
+                vec.clear();
+                vec.extend_from_slice(source);
+            }),
+            _ => *self = source.clone(),
+        }
+    }
+}
+
+impl Deref for DigitVec {
+    type Target = [BigDigit];
+
+    #[inline]
+    fn deref(&self) -> &[BigDigit] {
+        match self.kind() {
+            // Safety: inline digits are always initialized.
+            Kind::Inline { len } => unsafe { &self.data.inline[..len] },
+            // Safety: heap and static digits are initialized up to their length.
+            Kind::Heap { .. } | Kind::Static => unsafe {
+                let (ptr, len) = self.data.slice;
+                slice::from_raw_parts(ptr.as_ptr(), len)
+            },
+        }
+    }
+}
+
+impl DerefMut for DigitVec {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [BigDigit] {
+        match self.kind() {
+            // Safety: inline digits are always initialized.
+            Kind::Inline { len } => unsafe { &mut self.data.inline[..len] },
+            // Safety: heap digits are initialized up to their length, and owned exclusively.
+            Kind::Heap { .. } => unsafe {
+                let (ptr, len) = self.data.slice;
+                slice::from_raw_parts_mut(ptr.as_ptr(), len)
+            },
+            Kind::Static => {
+                *self = Self::from_slice(self);
+                self
+            }
+        }
+    }
+}
+
+impl PartialEq for DigitVec {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for DigitVec {}
+
+impl hash::Hash for DigitVec {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        // Hash the same as the slice, and therefore as `Vec` did.
+        (**self).hash(state);
+    }
+}
+
+impl From<Vec<BigDigit>> for DigitVec {
+    #[inline]
+    fn from(vec: Vec<BigDigit>) -> Self {
+        if vec.len() <= INLINE {
+            Self::from_slice(&vec)
+        } else {
+            Self::from_heap(vec)
+        }
+    }
+}
+
+impl Extend<BigDigit> for DigitVec {
+    fn extend<I: IntoIterator<Item = BigDigit>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for digit in iter {
+            self.push(digit);
+        }
+    }
+}
+
+impl FromIterator<BigDigit> for DigitVec {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = BigDigit>>(iter: I) -> Self {
+        let mut digits = Self::new();
+        digits.extend(iter);
+        digits
+    }
+}
+
+#[test]
+fn test_inline_and_heap() {
+    let mut v = DigitVec::new();
+    assert_eq!(v.capacity(), INLINE);
+    for i in 0..INLINE as BigDigit {
+        v.push(i + 1);
+    }
+    assert!(matches!(v.kind(), Kind::Inline { .. }));
+    v.push(0);
+    assert!(matches!(v.kind(), Kind::Heap { .. }));
+    assert_eq!(v.pop(), Some(0));
+    assert_eq!(v.clone().capacity(), INLINE);
+    v.shrink_to_fit();
+    assert!(matches!(v.kind(), Kind::Inline { .. }));
+    v.remove_prefix(1);
+    assert_eq!(v[0], 2);
+    v[0] = 7;
+    v.resize(INLINE + 2, 9);
+    assert_eq!(v[0], 7);
+    assert_eq!(v[INLINE + 1], 9);
+    assert_eq!(v.len(), INLINE + 2);
+    v.truncate(1);
+    let mut w = DigitVec::from(Vec::from([1; 10]));
+    w.clone_from(&v);
+    assert_eq!(&*w, &[7]);
+    assert!(w.capacity() >= 10);
+}
+
+#[test]
+fn test_static() {
+    static DIGITS: [BigDigit; INLINE + 2] = [1; INLINE + 2];
+    let mut v = DigitVec::from_static(&DIGITS);
+    assert!(matches!(v.clone().kind(), Kind::Static));
+    v.remove_prefix(1);
+    assert_eq!(v.pop(), Some(1));
+    assert!(matches!(v.kind(), Kind::Static));
+    v[0] = 2;
+    assert!(matches!(v.kind(), Kind::Inline { .. }));
+    assert_eq!(v[0], 2);
+    assert_eq!(&v[1..], &[1; INLINE - 1]);
+    let mut w = DigitVec::from_static(&DIGITS);
+    w.push(3);
+    assert_eq!(w.len(), INLINE + 3);
+    assert!(matches!(
+        DigitVec::from_static(&DIGITS[..1]).kind(),
+        Kind::Inline { len: 1 }
+    ));
+}
+
+#[test]
+fn test_size() {
+    use core::mem::size_of;
+
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(size_of::<DigitVec>(), size_of::<Vec<BigDigit>>());
+    }
+    assert_eq!(size_of::<Option<DigitVec>>(), size_of::<DigitVec>());
+}
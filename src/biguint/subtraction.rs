@@ -1,69 +1,18 @@
 use super::BigUint;
 
 use crate::big_digit::{self, BigDigit};
+use crate::digits::{__sub2, sbb};
 use crate::UsizePromotion;
 
 use core::cmp::Ordering::{Equal, Greater, Less};
 use core::ops::{Sub, SubAssign};
 use num_traits::CheckedSub;
 
-#[cfg(target_arch = "x86_64")]
-use core::arch::x86_64 as arch;
-
-#[cfg(target_arch = "x86")]
-use core::arch::x86 as arch;
-
-// Subtract with borrow:
-#[cfg(target_arch = "x86_64")]
-cfg_64!(
-    #[inline]
-    fn sbb(borrow: u8, a: u64, b: u64, out: &mut u64) -> u8 {
-        // Safety: There are absolutely no safety concerns with calling `_subborrow_u64`.
-        // It's just unsafe for API consistency with other intrinsics.
-        unsafe { arch::_subborrow_u64(borrow, a, b, out) }
-    }
-);
-
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-cfg_32!(
-    #[inline]
-    fn sbb(borrow: u8, a: u32, b: u32, out: &mut u32) -> u8 {
-        // Safety: There are absolutely no safety concerns with calling `_subborrow_u32`.
-        // It's just unsafe for API consistency with other intrinsics.
-        unsafe { arch::_subborrow_u32(borrow, a, b, out) }
-    }
-);
-
-// fallback for environments where we don't have a subborrow intrinsic
-// (copied from the standard library's `borrowing_sub`)
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-#[inline]
-fn sbb(borrow: u8, lhs: BigDigit, rhs: BigDigit, out: &mut BigDigit) -> u8 {
-    let (a, b) = lhs.overflowing_sub(rhs);
-    let (c, d) = a.overflowing_sub(borrow as BigDigit);
-    *out = c;
-    u8::from(b || d)
-}
-
 pub(super) fn sub2(a: &mut [BigDigit], b: &[BigDigit]) {
-    let mut borrow = 0;
-
     let len = Ord::min(a.len(), b.len());
-    let (a_lo, a_hi) = a.split_at_mut(len);
     let (b_lo, b_hi) = b.split_at(len);
 
-    for (a, b) in a_lo.iter_mut().zip(b_lo) {
-        borrow = sbb(borrow, *a, *b, a);
-    }
-
-    if borrow != 0 {
-        for a in a_hi {
-            borrow = sbb(borrow, *a, 0, a);
-            if borrow == 0 {
-                break;
-            }
-        }
-    }
+    let borrow = __sub2(a, b_lo);
 
     // note: we're _required_ to fail on underflow
     assert!(
@@ -104,6 +53,13 @@ fn sub2rev(a: &[BigDigit], b: &mut [BigDigit]) {
     );
 }
 
+pub(super) fn sub_into(out: &mut BigUint, a: &[BigDigit], b: &[BigDigit]) {
+    out.data.clear();
+    out.data.extend_from_slice(a);
+    sub2(&mut out.data, b);
+    out.normalize();
+}
+
 forward_val_val_binop!(impl Sub for BigUint, sub);
 forward_ref_ref_binop!(impl Sub for BigUint, sub);
 forward_val_assign!(impl SubAssign for BigUint, sub_assign);
@@ -170,7 +126,7 @@ impl Sub<BigUint> for u32 {
     cfg_digit!(
         #[inline]
         fn sub(self, mut other: BigUint) -> BigUint {
-            if other.data.len() == 0 {
+            if other.data.is_empty() {
                 other.data.push(self);
             } else {
                 sub2rev(&[self], &mut other.data[..]);
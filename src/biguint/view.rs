@@ -0,0 +1,418 @@
+//! Borrowed, read-only views of unsigned big integers stored in external digit slices.
+
+use super::convert::{digits_to_f32, digits_to_f64};
+use super::{addition, multiplication, subtraction};
+use super::{biguint_from_vec, BigUint, DigitVec};
+
+use crate::big_digit::BigDigit;
+use crate::format;
+
+use alloc::borrow::Cow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Rem, Sub};
+use num_traits::{PrimInt, ToPrimitive};
+
+/// The digits of a view, in whichever width they were given, without trailing zeros.
+#[derive(Clone, Copy)]
+enum Limbs<'a> {
+    U32(&'a [u32]),
+    U64(&'a [u64]),
+}
+
+/// Runs the same generic code on the digits, whatever their width.
+macro_rules! with_limbs {
+    ($limbs:expr, |$digits:ident| $body:expr) => {
+        match $limbs {
+            Limbs::U32($digits) => $body,
+            Limbs::U64($digits) => $body,
+        }
+    };
+}
+
+/// A borrowed view of an unsigned big integer stored as a slice of `u32` or `u64` digits.
+///
+/// The digits are ordered least significant digit first, and may have trailing zeros. Creating a
+/// view never copies them, so values can be read straight out of memory-mapped or otherwise
+/// foreign storage. Comparisons, [`bits`][Self::bits] and primitive conversions work directly on
+/// the borrowed digits, while formatting and arithmetic produce owned results.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{BigUint, BigUintRef};
+///
+/// let column: &[u64] = &[0, 1, 0];
+/// let view = BigUintRef::from_u64_slice(column);
+/// assert_eq!(view.bits(), 65);
+/// assert_eq!(view, BigUint::from(1u128 << 64));
+/// assert_eq!(&view * &BigUint::from(3u32), BigUint::from(3u128 << 64));
+/// assert_eq!(view.to_string(), "18446744073709551616");
+/// ```
+#[derive(Clone, Copy)]
+pub struct BigUintRef<'a> {
+    limbs: Limbs<'a>,
+}
+
+fn trim<T: PrimInt>(digits: &[T]) -> &[T] {
+    let len = digits
+        .iter()
+        .rposition(|d| !d.is_zero())
+        .map_or(0, |i| i + 1);
+    &digits[..len]
+}
+
+fn bits_of<T: PrimInt>(digits: &[T]) -> u64 {
+    match digits.last() {
+        None => 0,
+        Some(&hi) => {
+            let width = u64::from(T::zero().count_zeros());
+            digits.len() as u64 * width - u64::from(hi.leading_zeros())
+        }
+    }
+}
+
+fn to_u128_of<T: PrimInt + Into<u64>>(digits: &[T]) -> Option<u128> {
+    if bits_of(digits) > 128 {
+        return None;
+    }
+    let width = T::zero().count_zeros();
+    let n = digits.iter().enumerate().fold(0, |n, (i, &d)| {
+        n | u128::from(d.into()) << (i as u32 * width)
+    });
+    Some(n)
+}
+
+fn cmp_digits<T: Ord>(a: &[T], b: &[T]) -> Ordering {
+    match Ord::cmp(&a.len(), &b.len()) {
+        Ordering::Equal => Iterator::cmp(a.iter().rev(), b.iter().rev()),
+        other => other,
+    }
+}
+
+cfg_digit!(
+    fn limbs_of(n: &BigUint) -> Limbs<'_> {
+        Limbs::U32(&n.data)
+    }
+
+    fn limbs_of(n: &BigUint) -> Limbs<'_> {
+        Limbs::U64(&n.data)
+    }
+);
+
+cfg_digit!(
+    fn big_digits(limbs: Limbs<'_>) -> Cow<'_, [BigDigit]> {
+        match limbs {
+            Limbs::U32(digits) => Cow::Borrowed(digits),
+            Limbs::U64(digits) => {
+                let mut data = alloc::vec::Vec::with_capacity(digits.len() * 2);
+                for &d in digits {
+                    data.push(d as u32);
+                    data.push((d >> 32) as u32);
+                }
+                if data.last() == Some(&0) {
+                    data.pop();
+                }
+                Cow::Owned(data)
+            }
+        }
+    }
+
+    fn big_digits(limbs: Limbs<'_>) -> Cow<'_, [BigDigit]> {
+        match limbs {
+            Limbs::U32(digits) => {
+                Cow::Owned(digits.chunks(2).map(super::u32_chunk_to_u64).collect())
+            }
+            Limbs::U64(digits) => Cow::Borrowed(digits),
+        }
+    }
+);
+
+impl<'a> BigUintRef<'a> {
+    /// Creates a view of base 2<sup>32</sup> digits, ordered least significant digit first.
+    #[inline]
+    pub fn from_u32_slice(digits: &'a [u32]) -> Self {
+        BigUintRef {
+            limbs: Limbs::U32(trim(digits)),
+        }
+    }
+
+    /// Creates a view of base 2<sup>64</sup> digits, ordered least significant digit first.
+    #[inline]
+    pub fn from_u64_slice(digits: &'a [u64]) -> Self {
+        BigUintRef {
+            limbs: Limbs::U64(trim(digits)),
+        }
+    }
+
+    /// Returns `true` if the viewed value is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        with_limbs!(self.limbs, |digits| digits.is_empty())
+    }
+
+    /// Determines the fewest bits necessary to express the viewed value.
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        with_limbs!(self.limbs, |digits| bits_of(digits))
+    }
+
+    /// Copies the viewed value into an owned [`BigUint`].
+    pub fn to_biguint(&self) -> BigUint {
+        match big_digits(self.limbs) {
+            Cow::Borrowed(digits) => biguint_from_vec(DigitVec::from_slice(digits)),
+            Cow::Owned(data) => biguint_from_vec(data),
+        }
+    }
+
+    /// Returns the digits in the native width, only copying them if that's different.
+    #[inline]
+    pub(super) fn digits(&self) -> Cow<'a, [BigDigit]> {
+        big_digits(self.limbs)
+    }
+
+    /// Returns the number of `u32` digits, for comparing views of different widths.
+    fn u32_len(&self) -> usize {
+        match self.limbs {
+            Limbs::U32(digits) => digits.len(),
+            Limbs::U64(digits) => match digits.last() {
+                Some(&hi) => digits.len() * 2 - usize::from(hi >> 32 == 0),
+                None => 0,
+            },
+        }
+    }
+
+    fn u32_digit(&self, i: usize) -> u32 {
+        match self.limbs {
+            Limbs::U32(digits) => digits[i],
+            Limbs::U64(digits) => (digits[i / 2] >> (32 * (i % 2))) as u32,
+        }
+    }
+}
+
+impl<'a> From<&'a BigUint> for BigUintRef<'a> {
+    #[inline]
+    fn from(n: &'a BigUint) -> Self {
+        BigUintRef { limbs: limbs_of(n) }
+    }
+}
+
+impl From<BigUintRef<'_>> for BigUint {
+    #[inline]
+    fn from(view: BigUintRef<'_>) -> Self {
+        view.to_biguint()
+    }
+}
+
+impl PartialEq for BigUintRef<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BigUintRef<'_> {}
+
+impl PartialOrd for BigUintRef<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUintRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.limbs, other.limbs) {
+            (Limbs::U32(a), Limbs::U32(b)) => cmp_digits(a, b),
+            (Limbs::U64(a), Limbs::U64(b)) => cmp_digits(a, b),
+            _ => {
+                let len = self.u32_len();
+                match Ord::cmp(&len, &other.u32_len()) {
+                    Ordering::Equal => Iterator::cmp(
+                        (0..len).rev().map(|i| self.u32_digit(i)),
+                        (0..len).rev().map(|i| other.u32_digit(i)),
+                    ),
+                    other => other,
+                }
+            }
+        }
+    }
+}
+
+impl PartialEq<BigUint> for BigUintRef<'_> {
+    #[inline]
+    fn eq(&self, other: &BigUint) -> bool {
+        *self == BigUintRef::from(other)
+    }
+}
+
+impl PartialEq<BigUintRef<'_>> for BigUint {
+    #[inline]
+    fn eq(&self, other: &BigUintRef<'_>) -> bool {
+        BigUintRef::from(self) == *other
+    }
+}
+
+impl PartialOrd<BigUint> for BigUintRef<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(&BigUintRef::from(other)))
+    }
+}
+
+impl PartialOrd<BigUintRef<'_>> for BigUint {
+    #[inline]
+    fn partial_cmp(&self, other: &BigUintRef<'_>) -> Option<Ordering> {
+        Some(BigUintRef::from(self).cmp(other))
+    }
+}
+
+macro_rules! impl_view_fmt {
+    ($($Trait:ident: $prefix:expr, $radix:expr, $upper:expr;)*) => {$(
+        impl fmt::$Trait for BigUintRef<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                format::fmt_radix(f, true, $prefix, &self.to_biguint(), $radix, $upper)
+            }
+        }
+    )*};
+}
+
+impl_view_fmt! {
+    Debug: "", 10, false;
+    Display: "", 10, false;
+    LowerHex: "0x", 16, false;
+    UpperHex: "0x", 16, true;
+    Binary: "0b", 2, false;
+    Octal: "0o", 8, false;
+}
+
+impl ToPrimitive for BigUintRef<'_> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.to_u128().as_ref().and_then(u128::to_i64)
+    }
+
+    #[inline]
+    fn to_i128(&self) -> Option<i128> {
+        self.to_u128().as_ref().and_then(u128::to_i128)
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u128().as_ref().and_then(u128::to_u64)
+    }
+
+    #[inline]
+    fn to_u128(&self) -> Option<u128> {
+        with_limbs!(self.limbs, |digits| to_u128_of(digits))
+    }
+
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        let bits = self.bits();
+        Some(with_limbs!(self.limbs, |digits| digits_to_f32(
+            digits, bits
+        )))
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        let bits = self.bits();
+        Some(with_limbs!(self.limbs, |digits| digits_to_f64(
+            digits, bits
+        )))
+    }
+}
+
+fn add(a: BigUintRef<'_>, b: BigUintRef<'_>) -> BigUint {
+    let mut out = BigUint::ZERO;
+    addition::add_into(&mut out, &a.digits(), &b.digits());
+    out
+}
+
+fn sub(a: BigUintRef<'_>, b: BigUintRef<'_>) -> BigUint {
+    let mut out = BigUint::ZERO;
+    subtraction::sub_into(&mut out, &a.digits(), &b.digits());
+    out
+}
+
+fn mul(a: BigUintRef<'_>, b: BigUintRef<'_>) -> BigUint {
+    let mut out = BigUint::ZERO;
+    multiplication::mul_into(&mut out, &a.digits(), &b.digits());
+    out
+}
+
+// Addition, subtraction and multiplication read both operands in place.
+macro_rules! impl_view_binop {
+    ($(impl $Op:ident, $op:ident => $kernel:ident;)*) => {$(
+        impl $Op<&BigUintRef<'_>> for &BigUintRef<'_> {
+            type Output = BigUint;
+
+            #[inline]
+            fn $op(self, other: &BigUintRef<'_>) -> BigUint {
+                $kernel(*self, *other)
+            }
+        }
+
+        impl $Op<&BigUint> for &BigUintRef<'_> {
+            type Output = BigUint;
+
+            #[inline]
+            fn $op(self, other: &BigUint) -> BigUint {
+                $kernel(*self, other.into())
+            }
+        }
+
+        impl $Op<&BigUintRef<'_>> for &BigUint {
+            type Output = BigUint;
+
+            #[inline]
+            fn $op(self, other: &BigUintRef<'_>) -> BigUint {
+                $kernel(self.into(), *other)
+            }
+        }
+    )*};
+}
+
+impl_view_binop! {
+    impl Add, add => add;
+    impl Sub, sub => sub;
+    impl Mul, mul => mul;
+}
+
+// Division works on an owned dividend, so views are copied first.
+macro_rules! impl_view_divop {
+    ($(impl $Op:ident, $op:ident;)*) => {$(
+        impl $Op<&BigUintRef<'_>> for &BigUintRef<'_> {
+            type Output = BigUint;
+
+            #[inline]
+            fn $op(self, other: &BigUintRef<'_>) -> BigUint {
+                $Op::$op(self.to_biguint(), &other.to_biguint())
+            }
+        }
+
+        impl $Op<&BigUint> for &BigUintRef<'_> {
+            type Output = BigUint;
+
+            #[inline]
+            fn $op(self, other: &BigUint) -> BigUint {
+                $Op::$op(self.to_biguint(), other)
+            }
+        }
+
+        impl $Op<&BigUintRef<'_>> for &BigUint {
+            type Output = BigUint;
+
+            #[inline]
+            fn $op(self, other: &BigUintRef<'_>) -> BigUint {
+                $Op::$op(self, &other.to_biguint())
+            }
+        }
+    )*};
+}
+
+impl_view_divop! {
+    impl Div, div;
+    impl Rem, rem;
+}
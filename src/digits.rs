@@ -0,0 +1,422 @@
+//! Arithmetic on raw slices of digits, least significant first.
+//!
+//! These kernels never allocate, so they're shared by the heap-allocated [`BigUint`] and the
+//! fixed-size [`FixedBigUint`].
+//!
+//! [`BigUint`]: crate::BigUint
+//! [`FixedBigUint`]: crate::FixedBigUint
+
+use crate::big_digit::{self, BigDigit, DoubleBigDigit};
+
+use core::mem;
+use num_integer::Integer;
+
+cfg_x86_digit!(
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64 as arch;
+
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86 as arch;
+);
+
+// Add with carry:
+cfg_x86_digit!(
+    cfg_digit!(
+        #[inline]
+        #[allow(unused_unsafe)] // the intrinsic is safe in newer Rust
+        fn adc(carry: u8, a: u32, b: u32, out: &mut u32) -> u8 {
+            // Safety: There are absolutely no safety concerns with calling `_addcarry_u32`.
+            // It's just unsafe for API consistency with other intrinsics.
+            unsafe { arch::_addcarry_u32(carry, a, b, out) }
+        }
+
+        #[inline]
+        #[allow(unused_unsafe)] // the intrinsic is safe in newer Rust
+        fn adc(carry: u8, a: u64, b: u64, out: &mut u64) -> u8 {
+            // Safety: There are absolutely no safety concerns with calling `_addcarry_u64`.
+            // It's just unsafe for API consistency with other intrinsics.
+            unsafe { arch::_addcarry_u64(carry, a, b, out) }
+        }
+    );
+);
+
+// fallback for environments where we don't have an addcarry intrinsic
+// (copied from the standard library's `carrying_add`)
+cfg_not_x86_digit!(
+    #[inline]
+    fn adc(carry: u8, lhs: BigDigit, rhs: BigDigit, out: &mut BigDigit) -> u8 {
+        let (a, b) = lhs.overflowing_add(rhs);
+        let (c, d) = a.overflowing_add(carry as BigDigit);
+        *out = c;
+        u8::from(b || d)
+    }
+);
+
+// Subtract with borrow:
+cfg_x86_digit!(
+    cfg_digit!(
+        #[inline]
+        #[allow(unused_unsafe)] // the intrinsic is safe in newer Rust
+        pub(crate) fn sbb(borrow: u8, a: u32, b: u32, out: &mut u32) -> u8 {
+            // Safety: There are absolutely no safety concerns with calling `_subborrow_u32`.
+            // It's just unsafe for API consistency with other intrinsics.
+            unsafe { arch::_subborrow_u32(borrow, a, b, out) }
+        }
+
+        #[inline]
+        #[allow(unused_unsafe)] // the intrinsic is safe in newer Rust
+        pub(crate) fn sbb(borrow: u8, a: u64, b: u64, out: &mut u64) -> u8 {
+            // Safety: There are absolutely no safety concerns with calling `_subborrow_u64`.
+            // It's just unsafe for API consistency with other intrinsics.
+            unsafe { arch::_subborrow_u64(borrow, a, b, out) }
+        }
+    );
+);
+
+// fallback for environments where we don't have a subborrow intrinsic
+// (copied from the standard library's `borrowing_sub`)
+cfg_not_x86_digit!(
+    #[inline]
+    pub(crate) fn sbb(borrow: u8, lhs: BigDigit, rhs: BigDigit, out: &mut BigDigit) -> u8 {
+        let (a, b) = lhs.overflowing_sub(rhs);
+        let (c, d) = a.overflowing_sub(borrow as BigDigit);
+        *out = c;
+        u8::from(b || d)
+    }
+);
+
+/// Two argument addition of raw slices, `a += b`, returning the carry.
+///
+/// This is used when the data `Vec` might need to resize to push a non-zero carry, so we perform
+/// the addition first hoping that it will fit.
+///
+/// The caller _must_ ensure that `a` is at least as long as `b`.
+#[inline]
+pub(crate) fn __add2(a: &mut [BigDigit], b: &[BigDigit]) -> BigDigit {
+    debug_assert!(a.len() >= b.len());
+
+    let mut carry = 0;
+    let (a_lo, a_hi) = a.split_at_mut(b.len());
+
+    for (a, b) in a_lo.iter_mut().zip(b) {
+        carry = adc(carry, *a, *b, a);
+    }
+
+    if carry != 0 {
+        for a in a_hi {
+            carry = adc(carry, *a, 0, a);
+            if carry == 0 {
+                break;
+            }
+        }
+    }
+
+    carry as BigDigit
+}
+
+/// Two argument subtraction of raw slices, `a -= b`, returning the borrow.
+///
+/// The caller _must_ ensure that `a` is at least as long as `b`.
+#[inline]
+pub(crate) fn __sub2(a: &mut [BigDigit], b: &[BigDigit]) -> BigDigit {
+    debug_assert!(a.len() >= b.len());
+
+    let mut borrow = 0;
+    let (a_lo, a_hi) = a.split_at_mut(b.len());
+
+    for (a, b) in a_lo.iter_mut().zip(b) {
+        borrow = sbb(borrow, *a, *b, a);
+    }
+
+    if borrow != 0 {
+        for a in a_hi {
+            borrow = sbb(borrow, *a, 0, a);
+            if borrow == 0 {
+                break;
+            }
+        }
+    }
+
+    borrow as BigDigit
+}
+
+#[inline]
+pub(crate) fn mac_with_carry(
+    a: BigDigit,
+    b: BigDigit,
+    c: BigDigit,
+    acc: &mut DoubleBigDigit,
+) -> BigDigit {
+    *acc += DoubleBigDigit::from(a);
+    *acc += DoubleBigDigit::from(b) * DoubleBigDigit::from(c);
+    let lo = *acc as BigDigit;
+    *acc >>= big_digit::BITS;
+    lo
+}
+
+/// Three argument multiply accumulate, `acc += b * c`, returning the carry out of `acc`.
+///
+/// The caller _must_ ensure that `acc` is at least as long as `b`.
+pub(crate) fn __mac_digit(acc: &mut [BigDigit], b: &[BigDigit], c: BigDigit) -> BigDigit {
+    if c == 0 {
+        return 0;
+    }
+
+    let mut carry = 0;
+    let (a_lo, a_hi) = acc.split_at_mut(b.len());
+
+    for (a, &b) in a_lo.iter_mut().zip(b) {
+        *a = mac_with_carry(*a, b, c, &mut carry);
+    }
+
+    // Even with a full digit of carry in, `a + b * c + carry` fits in two digits, so the carry
+    // out always fits in one.
+    let carry = carry as BigDigit;
+    if a_hi.is_empty() {
+        carry
+    } else {
+        __add2(a_hi, &[carry])
+    }
+}
+
+// `x86` can only divide `u64` digits in software.
+cfg_digit!(
+    pub(crate) const FAST_DIV_WIDE: bool = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
+    pub(crate) const FAST_DIV_WIDE: bool = cfg!(target_arch = "x86_64");
+);
+
+cfg_not_x86_digit!(
+    /// Divide a two digit numerator by a one digit divisor, returns quotient and remainder:
+    ///
+    /// Note: the caller must ensure that both the quotient and remainder will fit into a single
+    /// digit. This is _not_ true for an arbitrary numerator/denominator.
+    ///
+    /// (This function also matches what the x86 divide instruction does).
+    #[inline]
+    fn div_wide(hi: BigDigit, lo: BigDigit, divisor: BigDigit) -> (BigDigit, BigDigit) {
+        debug_assert!(hi < divisor);
+
+        let lhs = big_digit::to_doublebigdigit(hi, lo);
+        let rhs = DoubleBigDigit::from(divisor);
+        ((lhs / rhs) as BigDigit, (lhs % rhs) as BigDigit)
+    }
+);
+
+cfg_x86_digit!(
+    /// x86 and x86_64 can use a real `div` instruction.
+    #[inline]
+    fn div_wide(hi: BigDigit, lo: BigDigit, divisor: BigDigit) -> (BigDigit, BigDigit) {
+        // This debug assertion covers the potential #DE for divisor==0 or a quotient too large for
+        // one register, otherwise in release mode it will become a target-specific fault like
+        // SIGFPE. This should never occur with the inputs from our few `div_wide` callers.
+        debug_assert!(hi < divisor);
+
+        // SAFETY: The `div` instruction only affects registers, reading the explicit operand as
+        // the divisor, and implicitly reading RDX:RAX or EDX:EAX as the dividend. The result is
+        // implicitly written back to RAX or EAX for the quotient and RDX or EDX for the remainder.
+        // No memory is used, and flags are not preserved.
+        unsafe {
+            let (div, rem);
+
+            cfg_digit!(
+                macro_rules! div {
+                    () => {
+                        "div {0:e}"
+                    };
+                }
+                macro_rules! div {
+                    () => {
+                        "div {0:r}"
+                    };
+                }
+            );
+
+            core::arch::asm!(
+                div!(),
+                in(reg) divisor,
+                inout("dx") hi => rem,
+                inout("ax") lo => div,
+                options(pure, nomem, nostack),
+            );
+
+            (div, rem)
+        }
+    }
+);
+
+/// For small divisors, we can divide without promoting to `DoubleBigDigit` by
+/// using half-size pieces of digit, like long-division.
+#[inline]
+fn div_half(rem: BigDigit, digit: BigDigit, divisor: BigDigit) -> (BigDigit, BigDigit) {
+    use crate::big_digit::{HALF, HALF_BITS};
+
+    debug_assert!(rem < divisor && divisor <= HALF);
+    let (hi, rem) = ((rem << HALF_BITS) | (digit >> HALF_BITS)).div_rem(&divisor);
+    let (lo, rem) = ((rem << HALF_BITS) | (digit & HALF)).div_rem(&divisor);
+    ((hi << HALF_BITS) | lo, rem)
+}
+
+/// Divides `a` by the single non-zero digit `b` in place, returning the remainder.
+#[inline]
+pub(crate) fn __div_rem_digit(a: &mut [BigDigit], b: BigDigit) -> BigDigit {
+    debug_assert!(b != 0);
+
+    let mut rem = 0;
+
+    if !FAST_DIV_WIDE && b <= big_digit::HALF {
+        for d in a.iter_mut().rev() {
+            let (q, r) = div_half(rem, *d, b);
+            *d = q;
+            rem = r;
+        }
+    } else {
+        for d in a.iter_mut().rev() {
+            let (q, r) = div_wide(rem, *d, b);
+            *d = q;
+            rem = r;
+        }
+    }
+
+    rem
+}
+
+/// Returns the remainder of `a` divided by the single non-zero digit `b`.
+#[inline]
+pub(crate) fn __rem_digit(a: &[BigDigit], b: BigDigit) -> BigDigit {
+    debug_assert!(b != 0);
+
+    let mut rem = 0;
+
+    if !FAST_DIV_WIDE && b <= big_digit::HALF {
+        for &digit in a.iter().rev() {
+            let (_, r) = div_half(rem, digit, b);
+            rem = r;
+        }
+    } else {
+        for &digit in a.iter().rev() {
+            let (_, r) = div_wide(rem, digit, b);
+            rem = r;
+        }
+    }
+
+    rem
+}
+
+/// Subtract a multiple.
+/// a -= b * c
+/// Returns a borrow (if a < b then borrow > 0).
+fn sub_mul_digit_same_len(a: &mut [BigDigit], b: &[BigDigit], c: BigDigit) -> BigDigit {
+    debug_assert!(a.len() == b.len());
+
+    // carry is between -big_digit::MAX and 0, so to avoid overflow we store
+    // offset_carry = carry + big_digit::MAX
+    let mut offset_carry = big_digit::MAX;
+
+    for (x, y) in a.iter_mut().zip(b) {
+        // We want to calculate sum = x - y * c + carry.
+        // sum >= -(big_digit::MAX * big_digit::MAX) - big_digit::MAX
+        // sum <= big_digit::MAX
+        // Offsetting sum by (big_digit::MAX << big_digit::BITS) puts it in DoubleBigDigit range.
+        let offset_sum = big_digit::to_doublebigdigit(big_digit::MAX, *x)
+            - big_digit::MAX as DoubleBigDigit
+            + offset_carry as DoubleBigDigit
+            - *y as DoubleBigDigit * c as DoubleBigDigit;
+
+        let (new_offset_carry, new_x) = big_digit::from_doublebigdigit(offset_sum);
+        offset_carry = new_offset_carry;
+        *x = new_x;
+    }
+
+    // Return the borrow.
+    big_digit::MAX - offset_carry
+}
+
+/// An implementation of the base division algorithm.
+/// Knuth, TAOCP vol 2 section 4.3.1, algorithm D, with an improvement from exercises 19-21.
+///
+/// The dividend is `a` with an extra most significant digit `a0`, which must not be greater than
+/// the top digit of `b`. The divisor `b` must have at least two digits and its highest bit set,
+/// and `a` must be at least as long. The quotient is written to `q`, which must have
+/// `a.len() - b.len() + 1` digits, and `a` is reduced in place to the remainder, which fits in
+/// its low `b.len()` digits.
+pub(crate) fn __div_rem(mut a0: BigDigit, a: &mut [BigDigit], b: &[BigDigit], q: &mut [BigDigit]) {
+    debug_assert!(a.len() >= b.len() && b.len() > 1);
+    debug_assert!(b.last().unwrap().leading_zeros() == 0);
+    debug_assert!(q.len() == a.len() - b.len() + 1);
+
+    // The algorithm works by incrementally calculating "guesses", q0, for the next digit of the
+    // quotient. Once we have any number q0 such that (q0 << j) * b <= a, we can set
+    //
+    //     q += q0 << j
+    //     a -= (q0 << j) * b
+    //
+    // and then iterate until a < b. Then, (q, a) will be our desired quotient and remainder.
+    //
+    // q0, our guess, is calculated by dividing the last three digits of a by the last two digits of
+    // b - this will give us a guess that is close to the actual quotient, but is possibly greater.
+    // It can only be greater by 1 and only in rare cases, with probability at most
+    // 2^-(big_digit::BITS-1) for random a, see TAOCP 4.3.1 exercise 21.
+    //
+    // If the quotient turns out to be too large, we adjust it by 1:
+    // q -= 1 << j
+    // a += b << j
+
+    // [b1, b0] are the two most significant digits of the divisor. They never change.
+    let b0 = b[b.len() - 1];
+    let b1 = b[b.len() - 2];
+    debug_assert!(a0 <= b0);
+
+    for j in (0..q.len()).rev() {
+        // The current dividend is a0 followed by a[..=top].
+        let top = b.len() + j - 1;
+        let a1 = a[top];
+        let a2 = a[top - 1];
+
+        // The first q0 estimate is [a1,a0] / b0. It will never be too small, it may be too large
+        // by at most 2.
+        let (mut q0, mut r) = if a0 < b0 {
+            let (q0, r) = div_wide(a0, a1, b0);
+            (q0, r as DoubleBigDigit)
+        } else {
+            debug_assert!(a0 == b0);
+            // Avoid overflowing q0, we know the quotient fits in BigDigit.
+            // [a1,a0] = b0 * (1<<BITS - 1) + (a0 + a1)
+            (big_digit::MAX, a0 as DoubleBigDigit + a1 as DoubleBigDigit)
+        };
+
+        // r = [a1,a0] - q0 * b0
+        //
+        // Now we want to compute a more precise estimate [a2,a1,a0] / [b1,b0] which can only be
+        // less or equal to the current q0.
+        //
+        // q0 is too large if:
+        // [a2,a1,a0] < q0 * [b1,b0]
+        // (r << BITS) + a2 < q0 * b1
+        while r <= big_digit::MAX as DoubleBigDigit
+            && big_digit::to_doublebigdigit(r as BigDigit, a2)
+                < q0 as DoubleBigDigit * b1 as DoubleBigDigit
+        {
+            q0 -= 1;
+            r += b0 as DoubleBigDigit;
+        }
+
+        // q0 is now either the correct quotient digit, or in rare cases 1 too large.
+        // Subtract (q0 << j) from a. This may overflow, in which case we will have to correct.
+
+        let mut borrow = sub_mul_digit_same_len(&mut a[j..=top], b, q0);
+        if borrow > a0 {
+            // q0 is too large. We need to add back one multiple of b.
+            q0 -= 1;
+            borrow -= __add2(&mut a[j..=top], b);
+        }
+        // The top digit of a, stored in a0, has now been zeroed.
+        debug_assert!(borrow == a0);
+
+        q[j] = q0;
+
+        // Take off the next top digit of a.
+        a0 = mem::replace(&mut a[top], 0);
+    }
+
+    a[b.len() - 1] = a0;
+}
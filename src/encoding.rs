@@ -0,0 +1,17 @@
+//! Binary encodings of big integers used by other data formats
+
+use crate::{DecodeBigIntError, DecodeErrorKind};
+
+mod cbor;
+pub(crate) mod der;
+pub(crate) mod leb128;
+pub(crate) mod pgp;
+pub(crate) mod ssh;
+
+/// Splits `n` bytes off the front of the input, if there are enough.
+fn split_at(bytes: &[u8], n: usize) -> Result<(&[u8], &[u8]), DecodeBigIntError> {
+    if bytes.len() < n {
+        return Err(DecodeBigIntError::new(DecodeErrorKind::Truncated));
+    }
+    Ok(bytes.split_at(n))
+}
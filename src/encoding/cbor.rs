@@ -0,0 +1,187 @@
+//! CBOR bignums, as specified by RFC 8949 section 3.4.3.
+#![cfg(feature = "cbor")]
+#![cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+
+use super::split_at;
+use crate::{BigInt, BigUint, DecodeBigIntError, DecodeErrorKind};
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use num_traits::{One, Signed, Zero};
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TAG: u8 = 6;
+
+const TAG_UNSIGNED_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+/// Writes a data item head with the shortest argument encoding.
+fn write_head(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    if arg < 24 {
+        out.push(major | arg as u8);
+    } else if let Ok(arg) = u8::try_from(arg) {
+        out.push(major | 24);
+        out.push(arg);
+    } else if let Ok(arg) = u16::try_from(arg) {
+        out.push(major | 25);
+        out.extend_from_slice(&arg.to_be_bytes());
+    } else if let Ok(arg) = u32::try_from(arg) {
+        out.push(major | 26);
+        out.extend_from_slice(&arg.to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&arg.to_be_bytes());
+    }
+}
+
+/// Reads a data item head, returning its major type, its argument, and the rest of the input.
+fn read_head(bytes: &[u8]) -> Result<(u8, u64, &[u8]), DecodeBigIntError> {
+    let (initial, rest) = split_at(bytes, 1)?;
+    let major = initial[0] >> 5;
+    let n = match initial[0] & 0x1f {
+        info @ 0..=23 => return Ok((major, u64::from(info), rest)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        // Reserved, or indefinite length, which doesn't apply to any integer item.
+        _ => return Err(DecodeBigIntError::new(DecodeErrorKind::InvalidLength)),
+    };
+    let (arg, rest) = split_at(rest, n)?;
+    let arg = arg.iter().fold(0, |acc, &b| acc << 8 | u64::from(b));
+    Ok((major, arg, rest))
+}
+
+/// Encodes a bignum, where a negative value is represented by its magnitude minus one.
+fn encode(negative: bool, n: &BigUint) -> Vec<u8> {
+    let bytes = if n.is_zero() {
+        Vec::new()
+    } else {
+        n.to_bytes_be()
+    };
+    let tag = if negative {
+        TAG_NEGATIVE_BIGNUM
+    } else {
+        TAG_UNSIGNED_BIGNUM
+    };
+
+    let mut out = Vec::with_capacity(bytes.len() + 10);
+    write_head(&mut out, MAJOR_TAG, tag);
+    write_head(&mut out, MAJOR_BYTES, bytes.len() as u64);
+    out.extend_from_slice(&bytes);
+    out
+}
+
+/// Decodes an integer or bignum, returning whether it is negative, and the magnitude minus one
+/// if it is, along with the rest of the input.
+fn decode(bytes: &[u8]) -> Result<(bool, BigUint, &[u8]), DecodeBigIntError> {
+    let (major, arg, rest) = read_head(bytes)?;
+    let negative = match (major, arg) {
+        (MAJOR_UNSIGNED, _) => return Ok((false, BigUint::from(arg), rest)),
+        (MAJOR_NEGATIVE, _) => return Ok((true, BigUint::from(arg), rest)),
+        (MAJOR_TAG, TAG_UNSIGNED_BIGNUM) => false,
+        (MAJOR_TAG, TAG_NEGATIVE_BIGNUM) => true,
+        _ => return Err(DecodeBigIntError::new(DecodeErrorKind::UnexpectedTag)),
+    };
+
+    // Indefinite-length byte strings are rejected by `read_head`.
+    let (major, len, rest) = read_head(rest)?;
+    if major != MAJOR_BYTES {
+        return Err(DecodeBigIntError::new(DecodeErrorKind::UnexpectedTag));
+    }
+    // No slice can be longer than `isize::MAX` bytes.
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|&len| len <= isize::MAX as usize)
+        .ok_or_else(|| DecodeBigIntError::new(DecodeErrorKind::InvalidLength))?;
+    let (content, rest) = split_at(rest, len)?;
+    Ok((negative, BigUint::from_bytes_be(content), rest))
+}
+
+impl BigUint {
+    /// Returns the CBOR encoding of the [`BigUint`] as an unsigned bignum, which is tag 2 followed
+    /// by a byte string of the big-endian magnitude without leading zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = BigUint::from(1u32) << 64u32;
+    /// assert_eq!(n.to_cbor(), vec![0xc2, 0x49, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    pub fn to_cbor(&self) -> Vec<u8> {
+        encode(false, self)
+    }
+
+    /// Decodes a [`BigUint`] from a CBOR data item at the start of `bytes`, returning it along
+    /// with the rest of the input.
+    ///
+    /// The item may be an unsigned integer or an unsigned bignum. Negative integers and bignums
+    /// are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    ///
+    /// let (n, rest) = BigUint::from_cbor(&[0x18, 0x64]).unwrap();
+    /// assert_eq!(n, BigUint::from(100u32));
+    /// assert!(rest.is_empty());
+    /// ```
+    pub fn from_cbor(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        match decode(bytes)? {
+            (false, n, rest) => Ok((n, rest)),
+            (true, ..) => Err(DecodeBigIntError::new(DecodeErrorKind::Negative)),
+        }
+    }
+}
+
+impl BigInt {
+    /// Returns the CBOR encoding of the [`BigInt`] as a bignum. Non-negative values use tag 2,
+    /// and negative values use tag 3 with the magnitude of `-1 - n`, each followed by a byte
+    /// string of the big-endian magnitude without leading zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let i = -(BigInt::from(1) << 64u32);
+    /// assert_eq!(i.to_cbor(), vec![0xc3, 0x48, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    /// ```
+    pub fn to_cbor(&self) -> Vec<u8> {
+        if self.is_negative() {
+            encode(true, &(self.magnitude() - 1u32))
+        } else {
+            encode(false, self.magnitude())
+        }
+    }
+
+    /// Decodes a [`BigInt`] from a CBOR data item at the start of `bytes`, returning it along
+    /// with the rest of the input.
+    ///
+    /// The item may be an unsigned or negative integer, or an unsigned or negative bignum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let (i, rest) = BigInt::from_cbor(&[0x38, 0x63, 0xf6]).unwrap();
+    /// assert_eq!(i, BigInt::from(-100));
+    /// assert_eq!(rest, &[0xf6]);
+    /// ```
+    pub fn from_cbor(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeBigIntError> {
+        let (negative, n, rest) = decode(bytes)?;
+        let i = if negative {
+            -BigInt::one() - BigInt::from(n)
+        } else {
+            BigInt::from(n)
+        };
+        Ok((i, rest))
+    }
+}
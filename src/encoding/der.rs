@@ -0,0 +1,73 @@
+//! ASN.1 DER `INTEGER` values, as specified by ITU-T X.690.
+
+use super::split_at;
+use crate::{DecodeBigIntError, DecodeErrorKind};
+
+use alloc::vec::Vec;
+
+const TAG_INTEGER: u8 = 0x02;
+
+/// Wraps minimal two's-complement content octets in the `INTEGER` tag and length.
+pub(crate) fn encode(content: &[u8]) -> Vec<u8> {
+    debug_assert!(!content.is_empty());
+    let len = content.len();
+    let len_bytes = len.to_be_bytes();
+    let len_bytes = &len_bytes[len.leading_zeros() as usize / 8..];
+
+    let mut out = Vec::with_capacity(2 + len_bytes.len() + len);
+    out.push(TAG_INTEGER);
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        out.push(0x80 | len_bytes.len() as u8);
+        out.extend_from_slice(len_bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Validates a complete `INTEGER` encoding, returning its content octets.
+pub(crate) fn decode(bytes: &[u8]) -> Result<&[u8], DecodeBigIntError> {
+    let err = |kind| Err(DecodeBigIntError::new(kind));
+
+    let (tag, rest) = split_at(bytes, 1)?;
+    if tag[0] != TAG_INTEGER {
+        return err(DecodeErrorKind::UnexpectedTag);
+    }
+
+    let (first, mut rest) = split_at(rest, 1)?;
+    let len = match first[0] {
+        len @ 0..=0x7f => usize::from(len),
+        // The indefinite form, and the reserved 0xff
+        0x80 | 0xff => return err(DecodeErrorKind::InvalidLength),
+        n => {
+            let (len_bytes, tail) = split_at(rest, usize::from(n & 0x7f))?;
+            rest = tail;
+            if len_bytes[0] == 0 {
+                return err(DecodeErrorKind::NonMinimal);
+            }
+            if len_bytes.len() > core::mem::size_of::<usize>() {
+                // It couldn't fit in memory anyway.
+                return err(DecodeErrorKind::InvalidLength);
+            }
+            let len = len_bytes
+                .iter()
+                .fold(0, |acc, &b| acc << 8 | usize::from(b));
+            if len < 0x80 {
+                return err(DecodeErrorKind::NonMinimal);
+            }
+            len
+        }
+    };
+
+    let (content, rest) = split_at(rest, len)?;
+    if !rest.is_empty() {
+        return err(DecodeErrorKind::TrailingData);
+    }
+    match content {
+        [] => err(DecodeErrorKind::InvalidLength),
+        [0x00, b, ..] if *b < 0x80 => err(DecodeErrorKind::NonMinimal),
+        [0xff, b, ..] if *b >= 0x80 => err(DecodeErrorKind::NonMinimal),
+        _ => Ok(content),
+    }
+}
@@ -0,0 +1,95 @@
+//! LEB128 variable-length integers, as used by DWARF, WebAssembly, and others.
+
+use crate::{BigInt, BigUint, DecodeBigIntError, DecodeErrorKind};
+
+use alloc::vec::Vec;
+use num_traits::{One, Signed};
+
+#[cfg(feature = "std")]
+use std::io;
+
+/// Appends `groups` 7-bit groups of the little-endian bytes, extended by `fill`.
+fn write_groups(bytes: &[u8], fill: u8, groups: u64, out: &mut Vec<u8>) {
+    let byte = |i: usize| u16::from(bytes.get(i).copied().unwrap_or(fill));
+    for g in 0..groups {
+        let bit = (g * 7) as usize;
+        let (i, shift) = (bit / 8, bit % 8);
+        let value = ((byte(i + 1) << 8 | byte(i)) >> shift) as u8 & 0x7f;
+        let more = if g + 1 < groups { 0x80 } else { 0 };
+        out.push(value | more);
+    }
+}
+
+pub(crate) fn write_unsigned(n: &BigUint, out: &mut Vec<u8>) {
+    let groups = Ord::max(1, (n.bits() + 6) / 7);
+    write_groups(&n.to_bytes_le(), 0, groups, out);
+}
+
+pub(crate) fn write_signed(i: &BigInt, out: &mut Vec<u8>) {
+    // Include the sign bit, which for negative values is the first one above `!i`.
+    let (bits, fill) = if i.is_negative() {
+        ((!i).bits() + 1, 0xff)
+    } else {
+        (i.bits() + 1, 0)
+    };
+    write_groups(&i.to_signed_bytes_le(), fill, (bits + 6) / 7, out);
+}
+
+/// Splits the 7-bit groups off the front of the input, through the first byte without the
+/// continuation bit.
+fn read_groups(bytes: &[u8]) -> Result<(Vec<u8>, &[u8]), DecodeBigIntError> {
+    match bytes.iter().position(|&b| b < 0x80) {
+        Some(end) => {
+            let groups = bytes[..=end].iter().map(|b| b & 0x7f).collect();
+            Ok((groups, &bytes[end + 1..]))
+        }
+        None => Err(DecodeBigIntError::new(DecodeErrorKind::Truncated)),
+    }
+}
+
+/// Reads the 7-bit groups one byte at a time, so nothing after them is consumed.
+#[cfg(feature = "std")]
+fn read_groups_io<R: io::Read>(mut reader: R) -> io::Result<Vec<u8>> {
+    let mut groups = Vec::new();
+    loop {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        groups.push(byte[0] & 0x7f);
+        if byte[0] < 0x80 {
+            return Ok(groups);
+        }
+    }
+}
+
+fn unsigned_from_groups(groups: &[u8]) -> BigUint {
+    BigUint::from_radix_le(groups, 128).unwrap()
+}
+
+fn signed_from_groups(groups: &[u8]) -> BigInt {
+    let n = BigInt::from(unsigned_from_groups(groups));
+    if groups.last().map_or(false, |&g| g & 0x40 != 0) {
+        n - (BigInt::one() << (7 * groups.len()))
+    } else {
+        n
+    }
+}
+
+pub(crate) fn read_unsigned(bytes: &[u8]) -> Result<(BigUint, &[u8]), DecodeBigIntError> {
+    let (groups, rest) = read_groups(bytes)?;
+    Ok((unsigned_from_groups(&groups), rest))
+}
+
+pub(crate) fn read_signed(bytes: &[u8]) -> Result<(BigInt, &[u8]), DecodeBigIntError> {
+    let (groups, rest) = read_groups(bytes)?;
+    Ok((signed_from_groups(&groups), rest))
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn read_unsigned_io<R: io::Read>(reader: R) -> io::Result<BigUint> {
+    read_groups_io(reader).map(|groups| unsigned_from_groups(&groups))
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn read_signed_io<R: io::Read>(reader: R) -> io::Result<BigInt> {
+    read_groups_io(reader).map(|groups| signed_from_groups(&groups))
+}
@@ -0,0 +1,32 @@
+//! OpenPGP multiprecision integers, as specified by RFC 4880 section 3.2.
+
+use super::split_at;
+use crate::{BigUint, DecodeBigIntError, DecodeErrorKind};
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use num_traits::Zero;
+
+/// Prefixes the magnitude bytes with the 16-bit bit count, if it fits.
+pub(crate) fn encode(n: &BigUint) -> Option<Vec<u8>> {
+    let bits = u16::try_from(n.bits()).ok()?;
+    let mut out = Vec::with_capacity(2 + (usize::from(bits) + 7) / 8);
+    out.extend_from_slice(&bits.to_be_bytes());
+    if !n.is_zero() {
+        out.extend_from_slice(&n.to_bytes_be());
+    }
+    Some(out)
+}
+
+/// Splits an MPI off the front of the input, returning its value and the rest of the input.
+/// Strict decoding also requires the bit count to be exact.
+pub(crate) fn decode(bytes: &[u8], strict: bool) -> Result<(BigUint, &[u8]), DecodeBigIntError> {
+    let (bits, rest) = split_at(bytes, 2)?;
+    let bits = u16::from_be_bytes([bits[0], bits[1]]);
+    let (magnitude, rest) = split_at(rest, (usize::from(bits) + 7) / 8)?;
+    let n = BigUint::from_bytes_be(magnitude);
+    if strict && n.bits() != u64::from(bits) {
+        return Err(DecodeBigIntError::new(DecodeErrorKind::NonMinimal));
+    }
+    Ok((n, rest))
+}
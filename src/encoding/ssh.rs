@@ -0,0 +1,36 @@
+//! SSH `mpint` values, as specified by RFC 4251 section 5.
+
+use super::split_at;
+use crate::{DecodeBigIntError, DecodeErrorKind};
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// Prefixes two's-complement content bytes with their 32-bit length.
+pub(crate) fn encode(content: &[u8]) -> Vec<u8> {
+    let len = u32::try_from(content.len()).expect("mpint is too long to encode");
+    let mut out = Vec::with_capacity(4 + content.len());
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(content);
+    out
+}
+
+/// Splits an `mpint` off the front of the input, returning its content bytes and the rest of
+/// the input. Strict decoding also requires the content to be minimal, with zero being empty.
+pub(crate) fn decode(bytes: &[u8], strict: bool) -> Result<(&[u8], &[u8]), DecodeBigIntError> {
+    let (len, rest) = split_at(bytes, 4)?;
+    let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]);
+    let len =
+        usize::try_from(len).map_err(|_| DecodeBigIntError::new(DecodeErrorKind::InvalidLength))?;
+    let (content, rest) = split_at(rest, len)?;
+    if strict {
+        match content {
+            [0x00] => return Err(DecodeBigIntError::new(DecodeErrorKind::NonMinimal)),
+            [0x00, b, ..] | [0xff, b, ..] if (content[0] ^ b) & 0x80 == 0 => {
+                return Err(DecodeBigIntError::new(DecodeErrorKind::NonMinimal));
+            }
+            _ => {}
+        }
+    }
+    Ok((content, rest))
+}
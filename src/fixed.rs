@@ -0,0 +1,659 @@
+//! Big integers with a fixed capacity, which never allocate.
+
+use crate::big_digit::{self, BigDigit};
+use crate::biguint::{biguint_from_vec, DigitVec, IntDigits};
+use crate::digits::{__add2, __div_rem, __div_rem_digit, __mac_digit, __sub2};
+use crate::{BigUint, TryFromBigIntError};
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign};
+use core::ops::{Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign};
+use core::slice;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
+use num_traits::{ConstZero, FromPrimitive, One, ToPrimitive, Zero};
+
+/// A big unsigned integer with a fixed capacity of `WORDS` 64-bit words, stored inline.
+///
+/// This uses the same digit-level algorithms as [`BigUint`], but never allocates. Like the
+/// primitive integers, the `checked_*` operations from [`num_traits`] return `None` on overflow,
+/// while the operators panic.
+///
+/// The capacity is [`Self::BITS`], `64 * WORDS`, on every target. The `u64-digit` and `u32-digit`
+/// features only change the digits that the algorithms work with, and therefore performance.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::FixedBigUint;
+/// use num_traits::{CheckedAdd, CheckedMul, FromPrimitive};
+///
+/// type U256 = FixedBigUint<4>;
+///
+/// let a = U256::from_u128(u128::MAX).unwrap();
+/// let b = a.checked_mul(&a).unwrap();
+/// assert_eq!(b.bits(), 256);
+/// assert_eq!(b + a + a, U256::MAX);
+/// assert_eq!(b.checked_mul(&a), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBigUint<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+/// The number of digits in each word of a [`FixedBigUint`].
+const WORD_DIGITS: usize = 64 / big_digit::BITS as usize;
+
+impl<const WORDS: usize> FixedBigUint<WORDS> {
+    /// A constant `FixedBigUint` with value 0.
+    pub const ZERO: Self = FixedBigUint { words: [0; WORDS] };
+
+    /// The largest value that fits, 2<sup>[`BITS`][Self::BITS]</sup> − 1.
+    pub const MAX: Self = FixedBigUint {
+        words: [u64::MAX; WORDS],
+    };
+
+    /// The capacity in bits.
+    pub const BITS: u64 = WORDS as u64 * 64;
+
+    /// The capacity in digits.
+    const DIGITS: usize = WORDS * WORD_DIGITS;
+
+    /// Creates and initializes a [`FixedBigUint`], or returns `None` if the value doesn't fit.
+    ///
+    /// The bytes are in big-endian byte order.
+    pub fn from_bytes_be(bytes: &[u8]) -> Option<Self> {
+        let mut n = Self::ZERO;
+        let digit_bytes = usize::from(big_digit::BITS / 8);
+        for (i, chunk) in bytes.rchunks(digit_bytes).enumerate() {
+            let digit = chunk
+                .iter()
+                .fold(0, |digit, &b| (digit << 8) | BigDigit::from(b));
+            if digit != 0 {
+                *n.data_mut().get_mut(i)? = digit;
+            }
+        }
+        Some(n)
+    }
+
+    /// Creates and initializes a [`FixedBigUint`], or returns `None` if the value doesn't fit.
+    ///
+    /// The bytes are in little-endian byte order.
+    pub fn from_bytes_le(bytes: &[u8]) -> Option<Self> {
+        let mut n = Self::ZERO;
+        let digit_bytes = usize::from(big_digit::BITS / 8);
+        for (i, chunk) in bytes.chunks(digit_bytes).enumerate() {
+            let digit = chunk
+                .iter()
+                .rev()
+                .fold(0, |digit, &b| (digit << 8) | BigDigit::from(b));
+            if digit != 0 {
+                *n.data_mut().get_mut(i)? = digit;
+            }
+        }
+        Some(n)
+    }
+
+    /// Determines the fewest bits necessary to express the [`FixedBigUint`].
+    pub fn bits(&self) -> u64 {
+        let digits = self.digits();
+        match digits.last() {
+            Some(&top) => {
+                let zeros = u64::from(top.leading_zeros());
+                digits.len() as u64 * u64::from(big_digit::BITS) - zeros
+            }
+            None => 0,
+        }
+    }
+
+    /// Returns `(self / other, self % other)`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `other` is zero.
+    #[inline]
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        self.checked_div_rem(other)
+            .expect("attempt to divide by zero")
+    }
+
+    /// Returns all the digits, including the leading zeros.
+    #[inline]
+    fn data(&self) -> &[BigDigit] {
+        // Safety: the words are also valid as `u32` digits, which are never more aligned.
+        unsafe { slice::from_raw_parts(self.words.as_ptr().cast(), Self::DIGITS) }
+    }
+
+    #[inline]
+    fn data_mut(&mut self) -> &mut [BigDigit] {
+        // Safety: the words are also valid as `u32` digits, which are never more aligned.
+        unsafe { slice::from_raw_parts_mut(self.words.as_mut_ptr().cast(), Self::DIGITS) }
+    }
+
+    /// Returns the significant digits, without the leading zeros.
+    #[inline]
+    fn digits(&self) -> &[BigDigit] {
+        let data = self.data();
+        let len = data.iter().rposition(|&d| d != 0).map_or(0, |i| i + 1);
+        &data[..len]
+    }
+
+    fn checked_div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        let u_len = self.digits().len();
+        let d = other.digits();
+        match d.len() {
+            0 => return None,
+            1 => {
+                let mut q = *self;
+                let r = __div_rem_digit(&mut q.data_mut()[..u_len], d[0]);
+                let mut rem = Self::ZERO;
+                if let Some(digit) = rem.data_mut().first_mut() {
+                    *digit = r;
+                }
+                return Some((q, rem));
+            }
+            d_len if d_len > u_len => return Some((Self::ZERO, *self)),
+            _ => {}
+        }
+
+        // Normalize so the divisor has its highest bit set, with an extra digit for the top of
+        // the dividend, as in `BigUint` division.
+        let d_len = d.len();
+        let shift = d[d_len - 1].leading_zeros();
+        let mut b = *other;
+        shl_digits(&mut b.data_mut()[..d_len], shift);
+        let mut a = *self;
+        let a0 = shl_digits(&mut a.data_mut()[..u_len], shift);
+
+        let mut q = Self::ZERO;
+        __div_rem(
+            a0,
+            &mut a.data_mut()[..u_len],
+            &b.data()[..d_len],
+            &mut q.data_mut()[..=u_len - d_len],
+        );
+        shr_digits(&mut a.data_mut()[..d_len], shift);
+        Some((q, a))
+    }
+}
+
+/// Shifts the digits left by fewer than `BITS` bits, returning the bits shifted out of the top.
+fn shl_digits(digits: &mut [BigDigit], shift: u32) -> BigDigit {
+    if shift == 0 {
+        return 0;
+    }
+    let mut carry = 0;
+    for d in digits {
+        let new_carry = *d >> (u32::from(big_digit::BITS) - shift);
+        *d = (*d << shift) | carry;
+        carry = new_carry;
+    }
+    carry
+}
+
+/// Shifts the digits right by fewer than `BITS` bits, discarding the bits shifted out.
+fn shr_digits(digits: &mut [BigDigit], shift: u32) {
+    if shift == 0 {
+        return;
+    }
+    let mut borrow = 0;
+    for d in digits.iter_mut().rev() {
+        let new_borrow = *d << (u32::from(big_digit::BITS) - shift);
+        *d = (*d >> shift) | borrow;
+        borrow = new_borrow;
+    }
+}
+
+impl<const WORDS: usize> Default for FixedBigUint<WORDS> {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const WORDS: usize> PartialOrd for FixedBigUint<WORDS> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const WORDS: usize> Ord for FixedBigUint<WORDS> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data().iter().rev().cmp(other.data().iter().rev())
+    }
+}
+
+impl<const WORDS: usize> CheckedAdd for FixedBigUint<WORDS> {
+    #[inline]
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        let mut sum = *self;
+        match __add2(sum.data_mut(), v.data()) {
+            0 => Some(sum),
+            _ => None,
+        }
+    }
+}
+
+impl<const WORDS: usize> CheckedSub for FixedBigUint<WORDS> {
+    #[inline]
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        let mut difference = *self;
+        match __sub2(difference.data_mut(), v.data()) {
+            0 => Some(difference),
+            _ => None,
+        }
+    }
+}
+
+impl<const WORDS: usize> CheckedMul for FixedBigUint<WORDS> {
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        let (a, b) = (self.digits(), v.digits());
+        if a.is_empty() || b.is_empty() {
+            return Some(Self::ZERO);
+        }
+        // The product has at least `a.len() + b.len() - 1` digits.
+        if a.len() + b.len() > Self::DIGITS + 1 {
+            return None;
+        }
+
+        // Long multiplication, where any carry out of the top digit is an overflow.
+        let mut product = Self::ZERO;
+        for (i, &bi) in b.iter().enumerate() {
+            if __mac_digit(&mut product.data_mut()[i..], a, bi) != 0 {
+                return None;
+            }
+        }
+        Some(product)
+    }
+}
+
+impl<const WORDS: usize> CheckedDiv for FixedBigUint<WORDS> {
+    #[inline]
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        self.checked_div_rem(v).map(|(q, _)| q)
+    }
+}
+
+impl<const WORDS: usize> CheckedRem for FixedBigUint<WORDS> {
+    #[inline]
+    fn checked_rem(&self, v: &Self) -> Option<Self> {
+        self.checked_div_rem(v).map(|(_, r)| r)
+    }
+}
+
+// Implements an operator by value and by reference, and its assignment operator, in terms of a
+// checked operation that panics with `$msg`.
+macro_rules! impl_fixed_binop {
+    ($Fixed:ident; $($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident => $checked:ident, $msg:expr;)*) => {$(
+        impl<const WORDS: usize> $Op<&$Fixed<WORDS>> for &$Fixed<WORDS> {
+            type Output = $Fixed<WORDS>;
+
+            #[inline]
+            fn $op(self, other: &$Fixed<WORDS>) -> $Fixed<WORDS> {
+                self.$checked(other).expect($msg)
+            }
+        }
+
+        impl<const WORDS: usize> $Op<$Fixed<WORDS>> for &$Fixed<WORDS> {
+            type Output = $Fixed<WORDS>;
+
+            #[inline]
+            fn $op(self, other: $Fixed<WORDS>) -> $Fixed<WORDS> {
+                self.$op(&other)
+            }
+        }
+
+        impl<const WORDS: usize> $Op<&$Fixed<WORDS>> for $Fixed<WORDS> {
+            type Output = $Fixed<WORDS>;
+
+            #[inline]
+            fn $op(self, other: &$Fixed<WORDS>) -> $Fixed<WORDS> {
+                (&self).$op(other)
+            }
+        }
+
+        impl<const WORDS: usize> $Op<$Fixed<WORDS>> for $Fixed<WORDS> {
+            type Output = $Fixed<WORDS>;
+
+            #[inline]
+            fn $op(self, other: $Fixed<WORDS>) -> $Fixed<WORDS> {
+                (&self).$op(&other)
+            }
+        }
+
+        impl<const WORDS: usize> $OpAssign<&$Fixed<WORDS>> for $Fixed<WORDS> {
+            #[inline]
+            fn $op_assign(&mut self, other: &$Fixed<WORDS>) {
+                *self = (&*self).$op(other);
+            }
+        }
+
+        impl<const WORDS: usize> $OpAssign<$Fixed<WORDS>> for $Fixed<WORDS> {
+            #[inline]
+            fn $op_assign(&mut self, other: $Fixed<WORDS>) {
+                *self = (&*self).$op(&other);
+            }
+        }
+    )*};
+}
+
+impl_fixed_binop! {
+    FixedBigUint;
+    Add, add, AddAssign, add_assign => checked_add, "attempt to add with overflow";
+    Sub, sub, SubAssign, sub_assign => checked_sub, "attempt to subtract with overflow";
+    Mul, mul, MulAssign, mul_assign => checked_mul, "attempt to multiply with overflow";
+    Div, div, DivAssign, div_assign => checked_div, "attempt to divide by zero";
+    Rem, rem, RemAssign, rem_assign => checked_rem, "attempt to calculate the remainder with a divisor of zero";
+}
+
+/// Shifts left like the primitive integers, discarding the bits shifted out of the top.
+///
+/// # Panics
+///
+/// This panics if the shift is at least [`BITS`][FixedBigUint::BITS].
+impl<const WORDS: usize> Shl<u32> for FixedBigUint<WORDS> {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self {
+        assert!(
+            u64::from(rhs) < Self::BITS,
+            "attempt to shift left with overflow"
+        );
+        let digits = (rhs / u32::from(big_digit::BITS)) as usize;
+        let mut n = Self::ZERO;
+        n.data_mut()[digits..].copy_from_slice(&self.data()[..Self::DIGITS - digits]);
+        shl_digits(
+            &mut n.data_mut()[digits..],
+            rhs % u32::from(big_digit::BITS),
+        );
+        n
+    }
+}
+
+/// Shifts right like the primitive integers.
+///
+/// # Panics
+///
+/// This panics if the shift is at least [`BITS`][FixedBigUint::BITS].
+impl<const WORDS: usize> Shr<u32> for FixedBigUint<WORDS> {
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self {
+        assert!(
+            u64::from(rhs) < Self::BITS,
+            "attempt to shift right with overflow"
+        );
+        let digits = (rhs / u32::from(big_digit::BITS)) as usize;
+        let mut n = Self::ZERO;
+        n.data_mut()[..Self::DIGITS - digits].copy_from_slice(&self.data()[digits..]);
+        shr_digits(
+            &mut n.data_mut()[..Self::DIGITS - digits],
+            rhs % u32::from(big_digit::BITS),
+        );
+        n
+    }
+}
+
+impl<const WORDS: usize> ShlAssign<u32> for FixedBigUint<WORDS> {
+    #[inline]
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = *self << rhs;
+    }
+}
+
+impl<const WORDS: usize> ShrAssign<u32> for FixedBigUint<WORDS> {
+    #[inline]
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<const WORDS: usize> Zero for FixedBigUint<WORDS> {
+    #[inline]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+}
+
+impl<const WORDS: usize> ConstZero for FixedBigUint<WORDS> {
+    const ZERO: Self = Self::ZERO;
+}
+
+/// # Panics
+///
+/// `one()` panics if `WORDS` is zero.
+impl<const WORDS: usize> One for FixedBigUint<WORDS> {
+    #[inline]
+    fn one() -> Self {
+        let mut n = Self::ZERO;
+        n.data_mut()[0] = 1;
+        n
+    }
+}
+
+impl<const WORDS: usize> ToPrimitive for FixedBigUint<WORDS> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.to_u128().as_ref().and_then(u128::to_i64)
+    }
+
+    #[inline]
+    fn to_i128(&self) -> Option<i128> {
+        self.to_u128().as_ref().and_then(u128::to_i128)
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u128().as_ref().and_then(u128::to_u64)
+    }
+
+    #[inline]
+    fn to_u128(&self) -> Option<u128> {
+        if self.bits() > 128 {
+            return None;
+        }
+        let n =
+            (self.digits().iter().rev()).fold(0, |n, &d| (n << big_digit::BITS) | u128::from(d));
+        Some(n)
+    }
+}
+
+impl<const WORDS: usize> FromPrimitive for FixedBigUint<WORDS> {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Self> {
+        Self::from_i128(i128::from(n))
+    }
+
+    #[inline]
+    fn from_i128(n: i128) -> Option<Self> {
+        u128::try_from(n).ok().and_then(Self::from_u128)
+    }
+
+    #[inline]
+    fn from_u64(n: u64) -> Option<Self> {
+        Self::from_u128(u128::from(n))
+    }
+
+    #[inline]
+    fn from_u128(n: u128) -> Option<Self> {
+        let (digits, len) = big_digit::from_u128(n);
+        let mut fixed = Self::ZERO;
+        fixed
+            .data_mut()
+            .get_mut(..len)?
+            .copy_from_slice(&digits[..len]);
+        Some(fixed)
+    }
+}
+
+// After `impl_fixed_binop!`, which it uses.
+mod signed;
+
+pub use self::signed::FixedBigInt;
+
+cfg_digit!(
+    const DECIMAL: (BigDigit, usize) = (1_000_000_000, 9);
+    const DECIMAL: (BigDigit, usize) = (10_000_000_000_000_000_000, 19);
+);
+
+/// Writes the decimal digits, dividing `digits` in place down to zero.
+fn write_decimal(digits: &mut [BigDigit], w: &mut dyn fmt::Write) -> fmt::Result {
+    let len = digits.iter().rposition(|&d| d != 0).map_or(0, |i| i + 1);
+    if len <= 1 {
+        return write!(w, "{}", digits.first().copied().unwrap_or(0));
+    }
+    let (base, width) = DECIMAL;
+    let rem = __div_rem_digit(&mut digits[..len], base);
+    write_decimal(&mut digits[..len], w)?;
+    write!(w, "{:01$}", rem, width)
+}
+
+/// Writes the digits in radix 2 or 16, from the most significant.
+fn write_power_of_two(
+    digits: &[BigDigit],
+    w: &mut dyn fmt::Write,
+    radix: u32,
+    upper: bool,
+) -> fmt::Result {
+    let width = usize::from(big_digit::BITS) / if radix == 2 { 1 } else { 4 };
+    let write_digit = |w: &mut dyn fmt::Write, d: BigDigit, width: usize| match (radix, upper) {
+        (2, _) => write!(w, "{:01$b}", d, width),
+        (_, false) => write!(w, "{:01$x}", d, width),
+        (_, true) => write!(w, "{:01$X}", d, width),
+    };
+    match digits.split_last() {
+        Some((&top, rest)) => {
+            write_digit(w, top, 0)?;
+            rest.iter()
+                .rev()
+                .try_for_each(|&d| write_digit(w, d, width))
+        }
+        None => w.write_str("0"),
+    }
+}
+
+/// Counts the characters written, to compute padding.
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Writes a number with the formatter's padding, like `Formatter::pad_integral` but without
+/// buffering the digits, which are written by `write_digits`.
+pub(crate) fn fmt_padded(
+    f: &mut fmt::Formatter<'_>,
+    is_nonnegative: bool,
+    prefix: &str,
+    write_digits: &dyn Fn(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let sign = match (is_nonnegative, f.sign_plus()) {
+        (false, _) => "-",
+        (true, true) => "+",
+        (true, false) => "",
+    };
+    let prefix = if f.alternate() { prefix } else { "" };
+
+    let padding = match f.width() {
+        Some(width) => {
+            let mut counter = Counter(sign.len() + prefix.len());
+            write_digits(&mut counter)?;
+            width.saturating_sub(counter.0)
+        }
+        None => 0,
+    };
+
+    if f.sign_aware_zero_pad() {
+        f.write_str(sign)?;
+        f.write_str(prefix)?;
+        (0..padding).try_for_each(|_| f.write_str("0"))?;
+        return write_digits(f);
+    }
+
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, (padding + 1) / 2),
+        Some(fmt::Alignment::Right) | None => (padding, 0),
+    };
+    let fill = f.fill();
+    (0..before).try_for_each(|_| fmt::Write::write_char(f, fill))?;
+    f.write_str(sign)?;
+    f.write_str(prefix)?;
+    write_digits(f)?;
+    (0..after).try_for_each(|_| fmt::Write::write_char(f, fill))
+}
+
+impl<const WORDS: usize> FixedBigUint<WORDS> {
+    /// Writes the value in radix 2, 10 or 16.
+    pub(crate) fn write_radix(
+        &self,
+        w: &mut dyn fmt::Write,
+        radix: u32,
+        upper: bool,
+    ) -> fmt::Result {
+        match radix {
+            10 => write_decimal({ *self }.data_mut(), w),
+            _ => write_power_of_two(self.digits(), w, radix, upper),
+        }
+    }
+}
+
+macro_rules! impl_fixed_fmt {
+    ($($Trait:ident: $prefix:expr, $radix:expr, $upper:expr;)*) => {$(
+        impl<const WORDS: usize> fmt::$Trait for FixedBigUint<WORDS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_padded(f, true, $prefix, &|w| self.write_radix(w, $radix, $upper))
+            }
+        }
+    )*};
+}
+
+impl_fixed_fmt! {
+    Debug: "", 10, false;
+    Display: "", 10, false;
+    LowerHex: "0x", 16, false;
+    UpperHex: "0x", 16, true;
+    Binary: "0b", 2, false;
+}
+
+impl<const WORDS: usize> From<FixedBigUint<WORDS>> for BigUint {
+    #[inline]
+    fn from(n: FixedBigUint<WORDS>) -> Self {
+        biguint_from_vec(DigitVec::from_slice(n.digits()))
+    }
+}
+
+impl<const WORDS: usize> TryFrom<&BigUint> for FixedBigUint<WORDS> {
+    type Error = TryFromBigIntError<()>;
+
+    #[inline]
+    fn try_from(value: &BigUint) -> Result<Self, TryFromBigIntError<()>> {
+        let digits = value.digits();
+        let mut n = Self::ZERO;
+        match n.data_mut().get_mut(..digits.len()) {
+            Some(data) => data.copy_from_slice(digits),
+            None => return Err(TryFromBigIntError::new(())),
+        }
+        Ok(n)
+    }
+}
+
+impl<const WORDS: usize> TryFrom<BigUint> for FixedBigUint<WORDS> {
+    type Error = TryFromBigIntError<BigUint>;
+
+    #[inline]
+    fn try_from(value: BigUint) -> Result<Self, TryFromBigIntError<BigUint>> {
+        Self::try_from(&value).map_err(|_| TryFromBigIntError::new(value))
+    }
+}
@@ -0,0 +1,346 @@
+use super::{fmt_padded, FixedBigUint};
+
+use crate::Sign::{self, Minus, NoSign, Plus};
+use crate::{BigInt, BigUint, TryFromBigIntError};
+
+use core::cmp::Ordering::{self, Equal, Greater, Less};
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign};
+use core::ops::{Sub, SubAssign};
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
+use num_traits::{ConstZero, FromPrimitive, One, ToPrimitive, Zero};
+
+/// A big signed integer with a fixed capacity of `WORDS` 64-bit words, stored inline.
+///
+/// Like [`BigInt`][crate::BigInt], this is a [`Sign`] and a magnitude, here a [`FixedBigUint`].
+/// The range is therefore symmetric, from −[`MAX`][Self::MAX] to `MAX`, so negation never
+/// overflows.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{FixedBigInt, FixedBigUint, Sign};
+/// use num_traits::{CheckedSub, FromPrimitive};
+///
+/// let max = FixedBigInt::<2>::MAX;
+/// assert_eq!(-max, FixedBigInt::new(Sign::Minus, FixedBigUint::MAX));
+/// assert_eq!((-max).checked_sub(&FixedBigInt::from_i32(1).unwrap()), None);
+/// assert_eq!(FixedBigInt::<2>::from_i64(-7).unwrap() / FixedBigInt::from_i64(2).unwrap(),
+///            FixedBigInt::from_i64(-3).unwrap());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBigInt<const WORDS: usize> {
+    sign: Sign,
+    magnitude: FixedBigUint<WORDS>,
+}
+
+impl<const WORDS: usize> FixedBigInt<WORDS> {
+    /// A constant `FixedBigInt` with value 0.
+    pub const ZERO: Self = FixedBigInt {
+        sign: NoSign,
+        magnitude: FixedBigUint::ZERO,
+    };
+
+    /// The largest value that fits, 2<sup>[`BITS`][FixedBigUint::BITS]</sup> − 1.
+    pub const MAX: Self = FixedBigInt {
+        sign: if WORDS == 0 { NoSign } else { Plus },
+        magnitude: FixedBigUint::MAX,
+    };
+
+    /// The smallest value that fits, −[`MAX`][Self::MAX].
+    pub const MIN: Self = FixedBigInt {
+        sign: if WORDS == 0 { NoSign } else { Minus },
+        magnitude: FixedBigUint::MAX,
+    };
+
+    /// Creates a [`FixedBigInt`] from a sign and a magnitude, like
+    /// [`BigInt::from_biguint`][crate::BigInt::from_biguint].
+    ///
+    /// The sign is ignored if the magnitude is zero, and vice versa.
+    #[inline]
+    pub fn new(sign: Sign, magnitude: FixedBigUint<WORDS>) -> Self {
+        if sign == NoSign || magnitude.is_zero() {
+            Self::ZERO
+        } else {
+            FixedBigInt { sign, magnitude }
+        }
+    }
+
+    /// Returns the sign of the [`FixedBigInt`].
+    #[inline]
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    /// Returns the magnitude of the [`FixedBigInt`].
+    #[inline]
+    pub fn magnitude(&self) -> &FixedBigUint<WORDS> {
+        &self.magnitude
+    }
+
+    /// Determines the fewest bits necessary to express the magnitude of the [`FixedBigInt`].
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        self.magnitude.bits()
+    }
+
+    /// Returns `(self / other, self % other)`, truncating towards zero like
+    /// [`BigInt`][crate::BigInt].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `other` is zero.
+    #[inline]
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        self.checked_div_rem(other)
+            .expect("attempt to divide by zero")
+    }
+
+    fn checked_div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        let (q, r) = self.magnitude.checked_div_rem(&other.magnitude)?;
+        Some((
+            Self::new(self.sign * other.sign, q),
+            Self::new(self.sign, r),
+        ))
+    }
+}
+
+impl<const WORDS: usize> Default for FixedBigInt<WORDS> {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const WORDS: usize> PartialOrd for FixedBigInt<WORDS> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const WORDS: usize> Ord for FixedBigInt<WORDS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scmp = self.sign.cmp(&other.sign);
+        if scmp != Equal {
+            return scmp;
+        }
+
+        match self.sign {
+            NoSign => Equal,
+            Plus => self.magnitude.cmp(&other.magnitude),
+            Minus => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl<const WORDS: usize> Neg for FixedBigInt<WORDS> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        FixedBigInt {
+            sign: -self.sign,
+            magnitude: self.magnitude,
+        }
+    }
+}
+
+impl<const WORDS: usize> Neg for &FixedBigInt<WORDS> {
+    type Output = FixedBigInt<WORDS>;
+
+    #[inline]
+    fn neg(self) -> FixedBigInt<WORDS> {
+        -*self
+    }
+}
+
+impl<const WORDS: usize> CheckedAdd for FixedBigInt<WORDS> {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        match (self.sign, v.sign) {
+            (_, NoSign) => Some(*self),
+            (NoSign, _) => Some(*v),
+            (a_sign, b_sign) if a_sign == b_sign => {
+                let magnitude = self.magnitude.checked_add(&v.magnitude)?;
+                Some(Self::new(a_sign, magnitude))
+            }
+            // Opposite signs subtract the smaller magnitude, which can't overflow.
+            (a_sign, b_sign) => Some(match self.magnitude.cmp(&v.magnitude) {
+                Less => Self::new(b_sign, v.magnitude - self.magnitude),
+                Greater => Self::new(a_sign, self.magnitude - v.magnitude),
+                Equal => Self::ZERO,
+            }),
+        }
+    }
+}
+
+impl<const WORDS: usize> CheckedSub for FixedBigInt<WORDS> {
+    #[inline]
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        self.checked_add(&-v)
+    }
+}
+
+impl<const WORDS: usize> CheckedMul for FixedBigInt<WORDS> {
+    #[inline]
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        let magnitude = self.magnitude.checked_mul(&v.magnitude)?;
+        Some(Self::new(self.sign * v.sign, magnitude))
+    }
+}
+
+impl<const WORDS: usize> CheckedDiv for FixedBigInt<WORDS> {
+    #[inline]
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        self.checked_div_rem(v).map(|(q, _)| q)
+    }
+}
+
+impl<const WORDS: usize> CheckedRem for FixedBigInt<WORDS> {
+    #[inline]
+    fn checked_rem(&self, v: &Self) -> Option<Self> {
+        self.checked_div_rem(v).map(|(_, r)| r)
+    }
+}
+
+impl_fixed_binop! {
+    FixedBigInt;
+    Add, add, AddAssign, add_assign => checked_add, "attempt to add with overflow";
+    Sub, sub, SubAssign, sub_assign => checked_sub, "attempt to subtract with overflow";
+    Mul, mul, MulAssign, mul_assign => checked_mul, "attempt to multiply with overflow";
+    Div, div, DivAssign, div_assign => checked_div, "attempt to divide by zero";
+    Rem, rem, RemAssign, rem_assign => checked_rem, "attempt to calculate the remainder with a divisor of zero";
+}
+
+impl<const WORDS: usize> Zero for FixedBigInt<WORDS> {
+    #[inline]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.sign == NoSign
+    }
+}
+
+impl<const WORDS: usize> ConstZero for FixedBigInt<WORDS> {
+    const ZERO: Self = Self::ZERO;
+}
+
+/// # Panics
+///
+/// `one()` panics if `WORDS` is zero.
+impl<const WORDS: usize> One for FixedBigInt<WORDS> {
+    #[inline]
+    fn one() -> Self {
+        Self::new(Plus, FixedBigUint::one())
+    }
+}
+
+impl<const WORDS: usize> ToPrimitive for FixedBigInt<WORDS> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.to_i128().as_ref().and_then(i128::to_i64)
+    }
+
+    #[inline]
+    fn to_i128(&self) -> Option<i128> {
+        let n = self.magnitude.to_u128()?;
+        match self.sign {
+            Plus | NoSign => n.to_i128(),
+            Minus if n <= 1 << 127 => Some((n as i128).wrapping_neg()),
+            Minus => None,
+        }
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u128().as_ref().and_then(u128::to_u64)
+    }
+
+    #[inline]
+    fn to_u128(&self) -> Option<u128> {
+        match self.sign {
+            Plus | NoSign => self.magnitude.to_u128(),
+            Minus => None,
+        }
+    }
+}
+
+impl<const WORDS: usize> FromPrimitive for FixedBigInt<WORDS> {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Self> {
+        Self::from_i128(i128::from(n))
+    }
+
+    #[inline]
+    fn from_i128(n: i128) -> Option<Self> {
+        let magnitude = FixedBigUint::from_u128(n.unsigned_abs())?;
+        let sign = if n < 0 { Minus } else { Plus };
+        Some(Self::new(sign, magnitude))
+    }
+
+    #[inline]
+    fn from_u64(n: u64) -> Option<Self> {
+        Self::from_u128(u128::from(n))
+    }
+
+    #[inline]
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Self::new(Plus, FixedBigUint::from_u128(n)?))
+    }
+}
+
+impl<const WORDS: usize> From<FixedBigUint<WORDS>> for FixedBigInt<WORDS> {
+    #[inline]
+    fn from(n: FixedBigUint<WORDS>) -> Self {
+        Self::new(Plus, n)
+    }
+}
+
+macro_rules! impl_fixed_fmt {
+    ($($Trait:ident: $prefix:expr, $radix:expr, $upper:expr;)*) => {$(
+        impl<const WORDS: usize> fmt::$Trait for FixedBigInt<WORDS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_padded(f, self.sign != Minus, $prefix, &|w| {
+                    self.magnitude.write_radix(w, $radix, $upper)
+                })
+            }
+        }
+    )*};
+}
+
+impl_fixed_fmt! {
+    Debug: "", 10, false;
+    Display: "", 10, false;
+    LowerHex: "0x", 16, false;
+    UpperHex: "0x", 16, true;
+    Binary: "0b", 2, false;
+}
+
+impl<const WORDS: usize> From<FixedBigInt<WORDS>> for BigInt {
+    #[inline]
+    fn from(n: FixedBigInt<WORDS>) -> Self {
+        BigInt::from_biguint(n.sign, BigUint::from(n.magnitude))
+    }
+}
+
+impl<const WORDS: usize> TryFrom<&BigInt> for FixedBigInt<WORDS> {
+    type Error = TryFromBigIntError<()>;
+
+    #[inline]
+    fn try_from(value: &BigInt) -> Result<Self, TryFromBigIntError<()>> {
+        let magnitude = FixedBigUint::try_from(value.magnitude())?;
+        Ok(Self::new(value.sign(), magnitude))
+    }
+}
+
+impl<const WORDS: usize> TryFrom<BigInt> for FixedBigInt<WORDS> {
+    type Error = TryFromBigIntError<BigInt>;
+
+    #[inline]
+    fn try_from(value: BigInt) -> Result<Self, TryFromBigIntError<BigInt>> {
+        Self::try_from(&value).map_err(|_| TryFromBigIntError::new(value))
+    }
+}
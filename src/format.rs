@@ -0,0 +1,220 @@
+//! Configurable formatting of big integers
+
+use crate::biguint::RadixDigits;
+use crate::BigUint;
+use crate::Sign::{self, Minus};
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+#[cfg(feature = "std")]
+use std::io;
+
+/// A builder for custom formatting of a [`BigInt`][crate::BigInt] or [`BigUint`].
+///
+/// This is created by [`BigInt::format()`][crate::BigInt::format] and
+/// [`BigUint::format()`], and implements [`Display`][fmt::Display] with the chosen options.
+/// Unlike post-processing the output of `to_string()`, the digits are only converted once and
+/// then written directly to the output, separators and all.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{BigInt, BigUint};
+///
+/// let n = BigUint::from(1234567u32);
+/// assert_eq!(n.format().group(3, ',').to_string(), "1,234,567");
+/// assert_eq!(n.format().radix(16).prefix(true).min_digits(8).to_string(), "0x0012d687");
+///
+/// let i = BigInt::from(-0xdead_beef_i64);
+/// assert_eq!(i.format().radix(16).group(4, '_').uppercase(true).to_string(), "-DEAD_BEEF");
+/// ```
+#[derive(Clone, Debug)]
+pub struct BigFormat<'a> {
+    sign: Sign,
+    data: &'a BigUint,
+    radix: u32,
+    group: Option<(usize, char)>,
+    min_digits: usize,
+    prefix: bool,
+    plus_sign: bool,
+    uppercase: bool,
+}
+
+impl<'a> BigFormat<'a> {
+    pub(crate) fn new(sign: Sign, data: &'a BigUint) -> Self {
+        BigFormat {
+            sign,
+            data,
+            radix: 10,
+            group: None,
+            min_digits: 0,
+            prefix: false,
+            plus_sign: false,
+            uppercase: false,
+        }
+    }
+
+    /// Sets the radix of the digits, which must be in the range `2...36`.
+    /// The default radix is 10.
+    pub fn radix(mut self, radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "The radix must be within 2...36");
+        self.radix = radix;
+        self
+    }
+
+    /// Separates every `size` digits with `separator`, counting from the least significant
+    /// digit, e.g. `group(3, ',')` for thousands separators in decimal, or `group(4, '_')` for
+    /// hexadecimal nibble pairs.
+    ///
+    /// Panics if `size` is zero.
+    pub fn group(mut self, size: usize, separator: char) -> Self {
+        assert!(size > 0, "digit group size must be at least 1");
+        self.group = Some((size, separator));
+        self
+    }
+
+    /// Pads the number with leading zeros until it has at least `digits` digits, not counting
+    /// any sign, prefix, or group separators.
+    pub fn min_digits(mut self, digits: usize) -> Self {
+        self.min_digits = digits;
+        self
+    }
+
+    /// Writes a `0b`, `0o`, or `0x` prefix for radix 2, 8, or 16 respectively.
+    /// Other radixes have no prefix, so this option is ignored for them.
+    pub fn prefix(mut self, prefix: bool) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Writes a `+` sign for numbers that are not negative.
+    /// Negative numbers always have a `-` sign.
+    pub fn plus_sign(mut self, plus_sign: bool) -> Self {
+        self.plus_sign = plus_sign;
+        self
+    }
+
+    /// Uses uppercase letters for digits above 9, which only matters for radixes greater than 10.
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    fn prefix_str(&self) -> &'static str {
+        match (self.prefix, self.radix) {
+            (true, 2) => "0b",
+            (true, 8) => "0o",
+            (true, 16) => "0x",
+            _ => "",
+        }
+    }
+
+    fn write_to<W: Write>(&self, w: &mut W) -> fmt::Result {
+        if self.sign == Minus {
+            w.write_char('-')?;
+        } else if self.plus_sign {
+            w.write_char('+')?;
+        }
+        w.write_str(self.prefix_str())?;
+
+        let digits = RadixDigits::new(self.data, self.radix);
+        let padding = self.min_digits.saturating_sub(digits.len());
+        let mut remaining = digits.len() + padding;
+
+        // Batch the output through a small buffer, rather than writing each digit and separator
+        // individually.
+        let mut buf = [0u8; 128];
+        let mut len = 0;
+        let mut push = |w: &mut W, d: u8| {
+            if len + 1 + 4 > buf.len() {
+                w.write_str(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })?;
+                len = 0;
+            }
+            buf[len] = d;
+            len += 1;
+            remaining -= 1;
+            if let Some((size, separator)) = self.group {
+                if remaining > 0 && remaining % size == 0 {
+                    len += separator.encode_utf8(&mut buf[len..]).len();
+                }
+            }
+            Ok(())
+        };
+        for _ in 0..padding {
+            push(w, b'0')?;
+        }
+        digits.write(self.uppercase, |s| s.bytes().try_for_each(|d| push(w, d)))?;
+        w.write_str(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+    }
+}
+
+impl fmt::Display for BigFormat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.width().is_some() || f.precision().is_some() {
+            // Alignment needs the full string to measure it.
+            let mut s = String::new();
+            self.write_to(&mut s)?;
+            f.pad(&s)
+        } else {
+            self.write_to(f)
+        }
+    }
+}
+
+/// Formats the digits like [`fmt::Formatter::pad_integral`], but writes them directly to the
+/// output when no padding is requested, instead of building a string first.
+pub(crate) fn fmt_radix(
+    f: &mut fmt::Formatter<'_>,
+    is_nonnegative: bool,
+    prefix: &str,
+    data: &BigUint,
+    radix: u32,
+    uppercase: bool,
+) -> fmt::Result {
+    let digits = RadixDigits::new(data, radix);
+    if f.width().is_some() {
+        let mut s = String::with_capacity(digits.len());
+        digits.write(uppercase, |d| s.write_str(d))?;
+        return f.pad_integral(is_nonnegative, prefix, &s);
+    }
+
+    if !is_nonnegative {
+        f.write_char('-')?;
+    } else if f.sign_plus() {
+        f.write_char('+')?;
+    }
+    if f.alternate() {
+        f.write_str(prefix)?;
+    }
+    digits.write(uppercase, |d| f.write_str(d))
+}
+
+/// Writes the sign and digits to a [`fmt::Write`], as `to_str_radix` would format them.
+pub(crate) fn write_radix<W: Write + ?Sized>(
+    w: &mut W,
+    sign: Sign,
+    data: &BigUint,
+    radix: u32,
+) -> fmt::Result {
+    let digits = RadixDigits::new(data, radix);
+    if sign == Minus {
+        w.write_char('-')?;
+    }
+    digits.write(false, |d| w.write_str(d))
+}
+
+/// Writes the sign and digits to an [`io::Write`], as `to_str_radix` would format them.
+#[cfg(feature = "std")]
+pub(crate) fn write_radix_io<W: io::Write + ?Sized>(
+    w: &mut W,
+    sign: Sign,
+    data: &BigUint,
+    radix: u32,
+) -> io::Result<()> {
+    let digits = RadixDigits::new(data, radix);
+    if sign == Minus {
+        w.write_all(b"-")?;
+    }
+    digits.write(false, |d| w.write_all(d.as_bytes()))
+}
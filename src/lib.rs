@@ -58,12 +58,35 @@
 //!
 //! See the "Features" section for instructions for enabling random number generation.
 //!
+//! ## Allocation
+//!
+//! The digits of a `BigInt` or `BigUint` always live in the global allocator, except for small
+//! values that are stored inline. There is no allocator parameter for arena or bump allocation
+//! yet, since the `Allocator` trait is unstable, and the `allocator-api2` crate that provides it
+//! on stable Rust needs a newer compiler than this crate's minimum of 1.60.
+//!
+//! Hot loops can still avoid most allocations by reusing buffers: reserve room once with
+//! [`BigUint::with_capacity_bits`], then write into existing values with the assignment
+//! operators or [`BigUint::add_into`] and its siblings, which keep the capacity they have.
+//! Values with a known bound fit in [`FixedBigUint`] and [`FixedBigInt`], which never allocate.
+//!
 //! ## Features
 //!
 //! The `std` crate feature is enabled by default, which enables [`std::error::Error`]
 //! implementations and some internal use of floating point approximations. This can be disabled by
 //! depending on `num-bigint` with `default-features = false`. Either way, the `alloc` crate is
-//! always required for heap allocation of the `BigInt`/`BigUint` digits.
+//! always required for heap allocation of the `BigInt`/`BigUint` digits. The fixed-capacity
+//! [`FixedBigUint`] and [`FixedBigInt`] never allocate themselves, but they come with the rest of
+//! the crate, so they need `alloc` too.
+//!
+//! ### Digit Size
+//!
+//! Internally, `BigUint` stores `u64` digits on 64-bit targets and `u32` digits otherwise. The
+//! `u64-digit` feature forces `u64` digits everywhere, which is often faster on 32-bit targets
+//! with efficient 64-bit multiplication like WebAssembly, and the `u32-digit` feature forces
+//! `u32` digits. If both are enabled, `u64-digit` takes precedence. The digit size doesn't change
+//! any results, the `serde` format, or the capacity of [`FixedBigUint`] and [`FixedBigInt`], only
+//! performance, so it's safe for any crate in the dependency graph to enable them.
 //!
 //! ### Random Generation
 //!
@@ -90,6 +113,9 @@
 //! [`Deserialize`][serde::Deserialize] for both `BigInt` and `BigUint`. Their serialized data is
 //! generated portably, regardless of platform differences like the internal digit size.
 //!
+//! The `cbor` feature adds `to_cbor` and `from_cbor` methods to both `BigInt` and `BigUint`,
+//! using the CBOR bignum tags 2 and 3 from RFC 8949. This doesn't require any other crate.
+//!
 //!
 //! ## Compatibility
 //!
@@ -114,6 +140,12 @@ mod macros;
 mod bigint;
 mod bigrand;
 mod biguint;
+mod digits;
+mod encoding;
+mod fixed;
+mod format;
+mod literal;
+mod sign;
 
 #[cfg(target_pointer_width = "32")]
 type UsizePromotion = u32;
@@ -125,15 +157,29 @@ type IsizePromotion = i32;
 #[cfg(target_pointer_width = "64")]
 type IsizePromotion = i64;
 
+/// The error type returned when parsing a big integer from a string fails.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseBigIntError {
     kind: BigIntErrorKind,
+    position: Option<usize>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum BigIntErrorKind {
+/// The reason for a [`ParseBigIntError`], as returned by [`ParseBigIntError::kind()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BigIntErrorKind {
+    /// The input was empty, or had nothing after its radix prefix.
     Empty,
+    /// The input had a sign, but no digits.
+    SignOnly,
+    /// The input contained a character that is not a digit in the radix.
     InvalidDigit,
+    /// The input had an underscore separator before its first digit.
+    MisplacedUnderscore,
+    /// The radix was outside the supported range `2...36`.
+    InvalidRadix,
+    /// The input exceeded a length limit of the parser.
+    TooLong,
 }
 
 impl ParseBigIntError {
@@ -141,20 +187,87 @@ impl ParseBigIntError {
         use crate::BigIntErrorKind::*;
         match self.kind {
             Empty => "cannot parse integer from empty string",
+            SignOnly => "cannot parse integer from a sign without digits",
             InvalidDigit => "invalid digit found in string",
+            MisplacedUnderscore => "underscore found before the first digit",
+            InvalidRadix => "radix is not supported",
+            TooLong => "number is too long to parse",
         }
     }
 
+    /// Returns the reason that parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::{BigInt, BigIntErrorKind};
+    ///
+    /// let err = "".parse::<BigInt>().unwrap_err();
+    /// assert_eq!(err.kind(), BigIntErrorKind::Empty);
+    /// let err = "-".parse::<BigInt>().unwrap_err();
+    /// assert_eq!(err.kind(), BigIntErrorKind::SignOnly);
+    /// let err = "12e4".parse::<BigInt>().unwrap_err();
+    /// assert_eq!(err.kind(), BigIntErrorKind::InvalidDigit);
+    /// ```
+    pub fn kind(&self) -> BigIntErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset of the first offending character in the input, if the error is
+    /// attributable to a particular character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigInt;
+    ///
+    /// let err = "-123_456_7x9".parse::<BigInt>().unwrap_err();
+    /// assert_eq!(err.position(), Some(10));
+    /// let err = "".parse::<BigInt>().unwrap_err();
+    /// assert_eq!(err.position(), None);
+    /// ```
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    fn new(kind: BigIntErrorKind, position: Option<usize>) -> Self {
+        Self { kind, position }
+    }
+
     fn empty() -> Self {
-        Self {
-            kind: BigIntErrorKind::Empty,
+        Self::new(BigIntErrorKind::Empty, None)
+    }
+
+    fn invalid(position: usize) -> Self {
+        Self::new(BigIntErrorKind::InvalidDigit, Some(position))
+    }
+
+    fn invalid_radix() -> Self {
+        Self::new(BigIntErrorKind::InvalidRadix, None)
+    }
+
+    fn misplaced_underscore(position: usize) -> Self {
+        Self::new(BigIntErrorKind::MisplacedUnderscore, Some(position))
+    }
+
+    fn too_long() -> Self {
+        Self::new(BigIntErrorKind::TooLong, None)
+    }
+
+    /// Adjusts the error for a sign that was stripped from the front of the input.
+    fn after_sign(mut self) -> Self {
+        if self.kind == BigIntErrorKind::Empty {
+            self.kind = BigIntErrorKind::SignOnly;
         }
+        self.shifted(1)
     }
 
-    fn invalid() -> Self {
-        Self {
-            kind: BigIntErrorKind::InvalidDigit,
+    /// Adjusts the error for `offset` bytes that were stripped from the front of the input.
+    fn shifted(mut self, offset: usize) -> Self {
+        if let Some(position) = &mut self.position {
+            *position += offset;
         }
+        self
     }
 }
 
@@ -172,6 +285,98 @@ impl std::error::Error for ParseBigIntError {
     }
 }
 
+/// The error type returned when decoding a big integer from a binary encoding fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeBigIntError {
+    kind: DecodeErrorKind,
+}
+
+/// The reason for a [`DecodeBigIntError`], as returned by [`DecodeBigIntError::kind()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeErrorKind {
+    /// The input ended before the encoded integer did.
+    Truncated,
+    /// The input continued after the encoded integer.
+    TrailingData,
+    /// The input did not start with the expected tag or type.
+    UnexpectedTag,
+    /// The encoded length was malformed or unsupported.
+    InvalidLength,
+    /// The integer was not encoded in the shortest form the encoding requires.
+    NonMinimal,
+    /// The encoded integer was negative, but was decoded as unsigned.
+    Negative,
+}
+
+impl DecodeBigIntError {
+    fn __description(&self) -> &str {
+        use crate::DecodeErrorKind::*;
+        match self.kind {
+            Truncated => "encoded integer is truncated",
+            TrailingData => "unexpected data after encoded integer",
+            UnexpectedTag => "unexpected tag for encoded integer",
+            InvalidLength => "invalid length for encoded integer",
+            NonMinimal => "encoded integer is not minimal",
+            Negative => "encoded integer is negative",
+        }
+    }
+
+    /// Returns the reason that decoding failed.
+    pub fn kind(&self) -> DecodeErrorKind {
+        self.kind
+    }
+
+    fn new(kind: DecodeErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for DecodeBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.__description().fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for DecodeBigIntError {
+    fn description(&self) -> &str {
+        self.__description()
+    }
+}
+
+/// The error type returned when a fallible operation like [`BigUint::try_pow`] can't allocate
+/// memory for a big integer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AllocBigIntError {
+    _priv: (),
+}
+
+impl AllocBigIntError {
+    fn new() -> Self {
+        Self { _priv: () }
+    }
+
+    fn __description(&self) -> &str {
+        "memory allocation failed for big integer"
+    }
+}
+
+impl fmt::Display for AllocBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.__description().fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for AllocBigIntError {
+    fn description(&self) -> &str {
+        self.__description()
+    }
+}
+
 /// The error type returned when a checked conversion regarding big integer fails.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct TryFromBigIntError<T> {
@@ -212,18 +417,53 @@ impl<T> fmt::Display for TryFromBigIntError<T> {
 }
 
 pub use crate::biguint::BigUint;
+pub use crate::biguint::BigUintParser;
+pub use crate::biguint::BigUintRef;
+pub use crate::biguint::Ones;
 pub use crate::biguint::ToBigUint;
 pub use crate::biguint::U32Digits;
 pub use crate::biguint::U64Digits;
+pub use crate::biguint::Zeros;
 
 pub use crate::bigint::BigInt;
-pub use crate::bigint::Sign;
+pub use crate::bigint::BigIntRef;
 pub use crate::bigint::ToBigInt;
 
+pub use crate::format::BigFormat;
+pub use crate::literal::LiteralOptions;
+
+pub use crate::fixed::{FixedBigInt, FixedBigUint};
+pub use crate::sign::Sign;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use crate::biguint::MaxBits;
+
 #[cfg(feature = "rand")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
 pub use crate::bigrand::{RandBigInt, RandomBits, UniformBigInt, UniformBigUint};
 
+// Support for the `biguint!` and `bigint!` macros, not public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::big_digit::BigDigit;
+    use crate::{BigInt, BigUint};
+
+    pub use crate::literal::constant::{is_negative, max_len, parse, trimmed_len, truncate};
+
+    pub type Digit = BigDigit;
+
+    #[inline]
+    pub const fn biguint(digits: &'static [Digit]) -> BigUint {
+        BigUint::from_static_digits(digits)
+    }
+
+    #[inline]
+    pub const fn bigint(negative: bool, digits: &'static [Digit]) -> BigInt {
+        BigInt::from_static_digits(negative, digits)
+    }
+}
+
 mod big_digit {
     // A [`BigDigit`] is a [`BigUint`]'s composing element.
     cfg_digit!(
@@ -265,4 +505,20 @@ mod big_digit {
     pub(crate) fn to_doublebigdigit(hi: BigDigit, lo: BigDigit) -> DoubleBigDigit {
         DoubleBigDigit::from(lo) | (DoubleBigDigit::from(hi) << BITS)
     }
+
+    pub(crate) const U128_DIGITS: usize = 128 / BITS as usize;
+
+    /// Split a `u128` into [`BigDigit`]s, least significant first, along with the number of
+    /// digits needed to hold it.
+    #[inline]
+    pub(crate) const fn from_u128(n: u128) -> ([BigDigit; U128_DIGITS], usize) {
+        let mut digits = [0; U128_DIGITS];
+        let mut i = 0;
+        while i < U128_DIGITS {
+            digits[i] = (n >> (i * BITS as usize)) as BigDigit;
+            i += 1;
+        }
+        let len = (128 - n.leading_zeros() as usize + BITS as usize - 1) / BITS as usize;
+        (digits, len)
+    }
 }
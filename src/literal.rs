@@ -0,0 +1,317 @@
+//! Parsing of integers written like Rust source literals
+
+use crate::{BigUint, ParseBigIntError};
+
+use num_traits::Num;
+
+/// Options for parsing integer literals with
+/// [`BigInt::parse_literal_with()`][crate::BigInt::parse_literal_with] and
+/// [`BigUint::parse_literal_with()`][BigUint::parse_literal_with].
+///
+/// A literal is an optional `+` or `-` sign, then an optional `0b`, `0o`, or `0x` radix prefix,
+/// then the digits, and finally an optional type suffix like `u64` or `i128`. Without a prefix,
+/// the digits are decimal.
+///
+/// The [default][LiteralOptions::new] accepts radix prefixes and `_` separators, but not type
+/// suffixes. The [strict][LiteralOptions::strict] options also reject separators, like the
+/// primitive integers' `FromStr`.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{BigInt, LiteralOptions};
+///
+/// let options = LiteralOptions::new().type_suffix(true);
+/// let i = BigInt::parse_literal_with("-0xffff_ffff_ffff_ffff_ffffu128", &options).unwrap();
+/// assert_eq!(i, BigInt::from(-0xffff_ffff_ffff_ffff_ffff_i128));
+///
+/// assert!(BigInt::parse_literal_with("1_000", &LiteralOptions::strict()).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LiteralOptions {
+    prefixes: bool,
+    underscores: bool,
+    type_suffix: bool,
+}
+
+/// The integer type suffixes, with no suffix being the tail of another.
+const TYPE_SUFFIXES: [&str; 12] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+impl LiteralOptions {
+    /// Accepts radix prefixes and `_` separators, but not type suffixes.
+    pub fn new() -> Self {
+        LiteralOptions {
+            prefixes: true,
+            underscores: true,
+            type_suffix: false,
+        }
+    }
+
+    /// Accepts radix prefixes, but neither `_` separators nor type suffixes.
+    pub fn strict() -> Self {
+        LiteralOptions {
+            prefixes: true,
+            underscores: false,
+            type_suffix: false,
+        }
+    }
+
+    /// Sets whether a `0b`, `0o`, or `0x` prefix selects radix 2, 8, or 16 respectively.
+    /// Otherwise, all literals are decimal.
+    pub fn prefixes(mut self, prefixes: bool) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Sets whether `_` may separate digits. As in Rust, a separator may not come before the
+    /// first digit of a literal without a prefix, but may follow a prefix, as in `0x_ff`.
+    ///
+    /// Rejected separators are reported as
+    /// [`MisplacedUnderscore`][crate::BigIntErrorKind::MisplacedUnderscore].
+    pub fn underscores(mut self, underscores: bool) -> Self {
+        self.underscores = underscores;
+        self
+    }
+
+    /// Sets whether the literal may end with a primitive integer type suffix, like `u8` or
+    /// `isize`. The suffix is only checked syntactically, and does not limit the value.
+    pub fn type_suffix(mut self, type_suffix: bool) -> Self {
+        self.type_suffix = type_suffix;
+        self
+    }
+
+    /// Parses a literal into whether it was negative and its magnitude.
+    pub(crate) fn parse(&self, s: &str) -> Result<(bool, BigUint), ParseBigIntError> {
+        let (negative, start) = match s.as_bytes().first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+
+        let mut end = s.len();
+        if self.type_suffix {
+            if let Some(suffix) = TYPE_SUFFIXES.iter().find(|&x| s[start..].ends_with(x)) {
+                end -= suffix.len();
+            }
+        }
+
+        let body = &s[start..end];
+        if body.is_empty() {
+            return Err(if start > 0 {
+                ParseBigIntError::empty().after_sign()
+            } else {
+                ParseBigIntError::empty()
+            });
+        }
+
+        let (radix, mut offset) = match body.get(..2) {
+            Some("0b") if self.prefixes => (2, start + 2),
+            Some("0o") if self.prefixes => (8, start + 2),
+            Some("0x") if self.prefixes => (16, start + 2),
+            _ => (10, start),
+        };
+        let mut digits = &s[offset..end];
+
+        if !self.underscores {
+            if let Some(i) = digits.find('_') {
+                return Err(ParseBigIntError::misplaced_underscore(offset + i));
+            }
+        } else if offset > start {
+            let trimmed = digits.trim_start_matches('_');
+            offset += digits.len() - trimmed.len();
+            digits = trimmed;
+        }
+
+        if digits.starts_with('+') {
+            // `from_str_radix` would take this as another sign.
+            return Err(ParseBigIntError::invalid(offset));
+        }
+
+        let n = BigUint::from_str_radix(digits, radix).map_err(|e| e.shifted(offset))?;
+        Ok((negative, n))
+    }
+}
+
+impl Default for LiteralOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compile-time parsing of literals for the [`biguint!`] and [`bigint!`] macros, which accepts the
+/// same syntax as the default [`LiteralOptions`]. Invalid literals panic, failing the build.
+///
+/// The macros parse into an array sized by [`max_len`], then copy the [`trimmed_len`] significant
+/// digits into an exact-size array, since const functions can't slice.
+pub(crate) mod constant {
+    use crate::big_digit::{BigDigit, DoubleBigDigit, BITS};
+
+    /// Splits a literal into whether it's negative, its radix, and where its digits start.
+    const fn split(s: &[u8]) -> (bool, u32, usize) {
+        let (negative, start) = match s.first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+        if s.len() >= start + 2 && s[start] == b'0' {
+            match s[start + 1] {
+                b'b' => return (negative, 2, start + 2),
+                b'o' => return (negative, 8, start + 2),
+                b'x' => return (negative, 16, start + 2),
+                _ => {}
+            }
+        }
+        if s.len() > start && s[start] == b'_' {
+            panic!("misplaced underscore in big integer literal");
+        }
+        (negative, 10, start)
+    }
+
+    pub const fn is_negative(s: &str) -> bool {
+        split(s.as_bytes()).0
+    }
+
+    /// Returns an upper bound of the number of digits needed for the literal's value.
+    pub const fn max_len(s: &str) -> usize {
+        let s = s.as_bytes();
+        let (_, radix, mut i) = split(s);
+        // Each decimal digit takes less than 4 bits.
+        let digit_bits = match radix {
+            2 => 1,
+            8 => 3,
+            _ => 4,
+        };
+        let mut count = 0;
+        while i < s.len() {
+            if s[i] != b'_' {
+                count += 1;
+            }
+            i += 1;
+        }
+        count * digit_bits / BITS as usize + 1
+    }
+
+    /// Parses the literal's magnitude into `N` digits, least significant first.
+    pub const fn parse<const N: usize>(s: &str, signed: bool) -> [BigDigit; N] {
+        let s = s.as_bytes();
+        let (negative, radix, mut i) = split(s);
+        if negative && !signed {
+            panic!("cannot parse unsigned big integer from negative literal");
+        }
+        let mut digits = [0; N];
+        let mut empty = true;
+        while i < s.len() {
+            let d = match s[i] {
+                b @ b'0'..=b'9' => b - b'0',
+                b @ b'a'..=b'z' => b - b'a' + 10,
+                b @ b'A'..=b'Z' => b - b'A' + 10,
+                b'_' => {
+                    i += 1;
+                    continue;
+                }
+                _ => panic!("invalid digit in big integer literal"),
+            };
+            if d as u32 >= radix {
+                panic!("invalid digit in big integer literal");
+            }
+            empty = false;
+
+            let mut carry = d as DoubleBigDigit;
+            let mut j = 0;
+            while j < N {
+                let t = digits[j] as DoubleBigDigit * radix as DoubleBigDigit + carry;
+                digits[j] = t as BigDigit;
+                carry = t >> BITS;
+                j += 1;
+            }
+            i += 1;
+        }
+        if empty {
+            panic!("cannot parse big integer from empty literal");
+        }
+        digits
+    }
+
+    /// Returns the number of digits without the trailing zeros.
+    pub const fn trimmed_len<const N: usize>(digits: &[BigDigit; N]) -> usize {
+        let mut len = N;
+        while len > 0 && digits[len - 1] == 0 {
+            len -= 1;
+        }
+        len
+    }
+
+    /// Copies the first `M` digits.
+    pub const fn truncate<const N: usize, const M: usize>(digits: &[BigDigit; N]) -> [BigDigit; M] {
+        let mut out = [0; M];
+        let mut i = 0;
+        while i < M {
+            out[i] = digits[i];
+            i += 1;
+        }
+        out
+    }
+}
+
+/// Creates a [`BigUint`] from a literal string at compile time.
+///
+/// The literal has the syntax of the default [`LiteralOptions`], like
+/// [`BigUint::parse_literal`], but without a sign. The result is a constant expression, so it can
+/// initialize `const` and `static` items. Values beyond 128 bits borrow their digits from static
+/// memory until they're modified. An invalid literal fails to compile.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{biguint, BigUint};
+///
+/// static P256: BigUint =
+///     biguint!("0xffffffff_00000001_00000000_00000000_00000000_ffffffff_ffffffff_ffffffff");
+///
+/// let p = (BigUint::from(1u32) << 256u32) - (BigUint::from(1u32) << 224u32)
+///     + (BigUint::from(1u32) << 192u32) + (BigUint::from(1u32) << 96u32) - 1u32;
+/// assert_eq!(P256, p);
+/// assert_eq!(biguint!("1_000_000"), BigUint::from(1_000_000u32));
+/// ```
+///
+/// ```compile_fail
+/// let n = num_bigint::biguint!("-1");
+/// ```
+#[macro_export]
+macro_rules! biguint {
+    ($literal:literal) => {{
+        const MAX_LEN: usize = $crate::__private::max_len($literal);
+        const RAW: [$crate::__private::Digit; MAX_LEN] = $crate::__private::parse($literal, false);
+        const LEN: usize = $crate::__private::trimmed_len(&RAW);
+        const DIGITS: [$crate::__private::Digit; LEN] = $crate::__private::truncate(&RAW);
+        $crate::__private::biguint(&DIGITS)
+    }};
+}
+
+/// Creates a [`BigInt`][crate::BigInt] from a literal string at compile time.
+///
+/// The literal has the syntax of the default [`LiteralOptions`], like
+/// [`BigInt::parse_literal`][crate::BigInt::parse_literal]. As with [`biguint!`], the result is a
+/// constant expression, and an invalid literal fails to compile.
+///
+/// # Examples
+///
+/// ```
+/// use num_bigint::{bigint, BigInt};
+///
+/// const N: BigInt = bigint!("-0xdead_beef_dead_beef_dead_beef_dead_beef_dead_beef");
+/// assert_eq!(N, -(BigInt::from(0xdead_beef_u32) << 128u32) - 0xdead_beef_dead_beef_dead_beef_dead_beef_u128);
+/// assert_eq!(bigint!("-0"), BigInt::ZERO);
+/// ```
+#[macro_export]
+macro_rules! bigint {
+    ($literal:literal) => {{
+        const MAX_LEN: usize = $crate::__private::max_len($literal);
+        const RAW: [$crate::__private::Digit; MAX_LEN] = $crate::__private::parse($literal, true);
+        const LEN: usize = $crate::__private::trimmed_len(&RAW);
+        const DIGITS: [$crate::__private::Digit; LEN] = $crate::__private::truncate(&RAW);
+        $crate::__private::bigint($crate::__private::is_negative($literal), &DIGITS)
+    }};
+}
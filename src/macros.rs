@@ -1,20 +1,38 @@
 #![allow(unused_macros)]
 
+// Digits are `u64` on 64-bit targets and `u32` otherwise, unless a size is forced by the
+// `u64-digit` or `u32-digit` feature. If both are enabled, `u64-digit` takes precedence.
+
 macro_rules! cfg_32 {
     ($($any:tt)+) => {
-        #[cfg(not(target_pointer_width = "64"))] $($any)+
+        #[cfg(not(any(
+            feature = "u64-digit",
+            all(target_pointer_width = "64", not(feature = "u32-digit")),
+        )))]
+        $($any)+
     }
 }
 
 macro_rules! cfg_32_or_test {
     ($($any:tt)+) => {
-        #[cfg(any(not(target_pointer_width = "64"), test))] $($any)+
+        #[cfg(any(
+            not(any(
+                feature = "u64-digit",
+                all(target_pointer_width = "64", not(feature = "u32-digit")),
+            )),
+            test,
+        ))]
+        $($any)+
     }
 }
 
 macro_rules! cfg_64 {
     ($($any:tt)+) => {
-        #[cfg(target_pointer_width = "64")] $($any)+
+        #[cfg(any(
+            feature = "u64-digit",
+            all(target_pointer_width = "64", not(feature = "u32-digit")),
+        ))]
+        $($any)+
     }
 }
 
@@ -32,6 +50,33 @@ macro_rules! cfg_digit_expr {
     };
 }
 
+/// Items for when x86 instructions can operate on a whole `BigDigit`, which is always the case on
+/// `x86_64`, but only for `u32` digits on `x86`. Miri takes the portable path instead.
+macro_rules! cfg_x86_digit {
+    ($($item:item)*) => {
+        $(
+            #[cfg(all(not(miri), target_arch = "x86_64"))]
+            $item
+
+            #[cfg(all(not(miri), target_arch = "x86"))]
+            cfg_32!($item);
+        )*
+    };
+}
+
+/// The complement of `cfg_x86_digit!`, for portable fallbacks.
+macro_rules! cfg_not_x86_digit {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(miri, not(any(target_arch = "x86", target_arch = "x86_64"))))]
+            $item
+
+            #[cfg(all(not(miri), target_arch = "x86"))]
+            cfg_64!($item);
+        )*
+    };
+}
+
 macro_rules! forward_val_val_binop {
     (impl $imp:ident for $res:ty, $method:ident) => {
         impl $imp<$res> for $res {
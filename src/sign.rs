@@ -0,0 +1,38 @@
+use core::ops::{Mul, Neg};
+
+use self::Sign::{Minus, NoSign, Plus};
+
+/// A `Sign` is a [`BigInt`][crate::BigInt]'s composing element.
+#[derive(PartialEq, PartialOrd, Eq, Ord, Copy, Clone, Debug, Hash)]
+pub enum Sign {
+    Minus,
+    NoSign,
+    Plus,
+}
+
+impl Neg for Sign {
+    type Output = Self;
+
+    /// Negate `Sign` value.
+    #[inline]
+    fn neg(self) -> Self {
+        match self {
+            Minus => Plus,
+            NoSign => NoSign,
+            Plus => Minus,
+        }
+    }
+}
+
+impl Mul<Self> for Sign {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        match (self, other) {
+            (NoSign, _) | (_, NoSign) => NoSign,
+            (Plus, Plus) | (Minus, Minus) => Plus,
+            (Plus, Minus) | (Minus, Plus) => Minus,
+        }
+    }
+}
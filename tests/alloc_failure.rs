@@ -0,0 +1,92 @@
+//! Fallible operations must report an error for every allocation that fails, including the
+//! temporaries, instead of aborting the process.
+
+use num_bigint::{BigInt, BigUint};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Debug;
+
+/// Counts the allocations of the current thread, and fails the one at index `FAIL_AT`.
+struct FailingAlloc;
+
+thread_local! {
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+    static FAIL_AT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+unsafe impl GlobalAlloc for FailingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let count = COUNT.with(|c| c.replace(c.get() + 1));
+        if count == FAIL_AT.with(Cell::get) {
+            core::ptr::null_mut()
+        } else {
+            System.alloc(layout)
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let count = COUNT.with(|c| c.replace(c.get() + 1));
+        if count == FAIL_AT.with(Cell::get) {
+            core::ptr::null_mut()
+        } else {
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: FailingAlloc = FailingAlloc;
+
+/// Runs `f` once to count its allocations, then once more failing each of them in turn.
+fn check_each_failure<T, E>(f: impl Fn() -> Result<T, E>)
+where
+    T: PartialEq + Debug,
+    E: Debug,
+{
+    COUNT.with(|c| c.set(0));
+    let expected = f().unwrap();
+    let allocations = COUNT.with(Cell::get);
+    assert!(allocations > 0);
+
+    for fail_at in 0..allocations {
+        COUNT.with(|c| c.set(0));
+        FAIL_AT.with(|c| c.set(fail_at));
+        let result = f();
+        FAIL_AT.with(|c| c.set(usize::MAX));
+        assert!(result.is_err(), "allocation {} didn't fail", fail_at);
+    }
+
+    COUNT.with(|c| c.set(0));
+    assert_eq!(f().unwrap(), expected);
+}
+
+fn big(bits: u64) -> BigUint {
+    // A number with all kinds of digits, so none of the partial products vanish.
+    (BigUint::from(3u32).pow(((bits as f64) / 1.585) as u32)) | BigUint::from(1u32) << bits
+}
+
+#[test]
+fn test_try_mul() {
+    // Long multiplication, Karatsuba, Half-Karatsuba and Toom-3.
+    for &(x_bits, y_bits) in &[(1000, 1500), (5000, 6000), (5000, 30000), (40000, 50000)] {
+        let x = big(x_bits);
+        let y = big(y_bits);
+        check_each_failure(|| x.try_mul(&y));
+
+        let x = -BigInt::from(x);
+        let y = BigInt::from(y);
+        check_each_failure(|| x.try_mul(&y));
+    }
+}
+
+#[test]
+fn test_try_pow() {
+    let x = big(20000);
+    check_each_failure(|| x.try_pow(3));
+    check_each_failure(|| x.try_pow(4));
+}
@@ -1,6 +1,6 @@
 use num_bigint::BigUint;
 use num_bigint::Sign::{Minus, NoSign, Plus};
-use num_bigint::{BigInt, ToBigInt};
+use num_bigint::{bigint, BigInt, BigIntErrorKind, BigIntRef, LiteralOptions, ToBigInt};
 
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::collections::hash_map::RandomState;
@@ -146,6 +146,62 @@ fn test_to_signed_bytes_be() {
     check("128", vec![0, 0x80]);
 }
 
+#[test]
+fn test_to_signed_bytes_padded() {
+    let values = [
+        0,
+        1,
+        -1,
+        127,
+        128,
+        -128,
+        -129,
+        255,
+        256,
+        -256,
+        i64::MAX.into(),
+        i64::MIN.into(),
+        i128::MAX,
+        i128::MIN,
+        i128::MIN + 1,
+        -0x1234_5678_9abc_def0_1234_5678,
+    ];
+    for &n in &values {
+        let i = BigInt::from(n);
+        let min = i.to_signed_bytes_le().len();
+        for width in 0..=20 {
+            let (be, le) = (
+                i.to_signed_bytes_be_padded(width),
+                i.to_signed_bytes_le_padded(width),
+            );
+            if width < min && !(n == 0 && width == 0) {
+                assert!(be.is_err() && le.is_err(), "{} in {} bytes", n, width);
+                continue;
+            }
+            let le = le.unwrap();
+            let fill = if n < 0 { 0xff } else { 0 };
+            let expected: Vec<u8> = (0..width)
+                .map(|k| n.to_le_bytes().get(k).copied().unwrap_or(fill))
+                .collect();
+            assert_eq!(le, expected, "{} in {} bytes", n, width);
+
+            let mut be = be.unwrap();
+            be.reverse();
+            assert_eq!(be, expected);
+        }
+    }
+
+    // The buffer is untouched on failure.
+    let mut buf = [0xaa; 2];
+    assert!(BigInt::from(-0x8001)
+        .write_signed_bytes_be(&mut buf)
+        .is_err());
+    assert!(BigInt::from(0x8000)
+        .write_signed_bytes_le(&mut buf)
+        .is_err());
+    assert_eq!(buf, [0xaa; 2]);
+}
+
 #[test]
 fn test_from_signed_bytes_be() {
     fn check(s: &[u8], result: &str) {
@@ -751,6 +807,117 @@ fn test_mul() {
     }
 }
 
+#[test]
+fn test_view() {
+    let big = BigInt::from(3u32).pow(100u32);
+    let values = [
+        BigInt::zero(),
+        BigInt::one(),
+        BigInt::from(i64::MIN),
+        BigInt::from(i128::MIN),
+        BigInt::from(i128::MAX),
+        -&big,
+        big.clone(),
+        &big + 1u32,
+    ];
+    for a in &values {
+        let (sign, u32_digits) = a.to_u32_digits();
+        let (_, u64_digits) = a.to_u64_digits();
+        let views = [
+            BigIntRef::from(a),
+            BigIntRef::from_u32_slice(sign, &u32_digits),
+            BigIntRef::from_u64_slice(sign, &u64_digits),
+        ];
+        for view in &views {
+            assert_eq!(*view, *a);
+            assert_eq!(view.to_bigint(), *a);
+            assert_eq!(view.sign(), a.sign());
+            assert_eq!(view.magnitude(), *a.magnitude());
+            assert_eq!(view.bits(), a.bits());
+            assert_eq!(view.to_i64(), a.to_i64());
+            assert_eq!(view.to_i128(), a.to_i128());
+            assert_eq!(view.to_u128(), a.to_u128());
+            assert_eq!(view.to_f64(), a.to_f64());
+            assert_eq!(view.to_string(), a.to_string());
+            assert_eq!(format!("{:b}", view), format!("{:b}", a));
+
+            for b in &values {
+                let other = BigIntRef::from(b);
+                assert_eq!(view.cmp(&other), a.cmp(b));
+                assert_eq!(view.partial_cmp(b), a.partial_cmp(b));
+                assert_eq!(view + b, a + b);
+                assert_eq!(view - &other, a - b);
+                assert_eq!(b - view, b - a);
+                assert_eq!(view * b, a * b);
+                if !b.is_zero() {
+                    assert_eq!(view / &other, a / b);
+                    assert_eq!(view % b, a % b);
+                }
+            }
+        }
+    }
+
+    // The sign and magnitude are normalized together.
+    let view = BigIntRef::from_u32_slice(Minus, &[0, 0]);
+    assert_eq!(view.sign(), NoSign);
+    assert_eq!(view, BigInt::zero());
+    let view = BigIntRef::from_u64_slice(NoSign, &[1]);
+    assert!(view.magnitude().is_zero());
+}
+
+#[test]
+fn test_add_mul() {
+    fn check(acc: &BigInt, a: &BigInt, b: &BigInt) {
+        let mut x = acc.clone();
+        x.add_mul(a, b);
+        assert_eq!(x, acc + a * b, "{} + {} * {}", acc, a, b);
+        let mut x = acc.clone();
+        x.sub_mul(a, b);
+        assert_eq!(x, acc - a * b, "{} - {} * {}", acc, a, b);
+    }
+
+    // Include lengths that take the Karatsuba and Toom-3 paths.
+    let big = BigInt::from(3u32).pow(2000u32);
+    let values = [
+        BigInt::zero(),
+        BigInt::one(),
+        BigInt::from(u64::MAX),
+        BigInt::from(5u32).pow(100u32),
+        &big - 1u32,
+        big.clone(),
+        &big * &big,
+        BigInt::from(3u32).pow(12000u32),
+    ];
+    for a in &values {
+        for b in &values {
+            let products = [a * b, a * b + 1u32, a * b - 1u32];
+            for acc in values.iter().chain(&products) {
+                for (a, b) in [(a, b), (&-a, b), (a, &-b), (&-a, &-b)] {
+                    check(acc, a, b);
+                    check(&-acc, a, b);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_add_mul_digit() {
+    let big = BigInt::from(7u32).pow(50u32);
+    for a in [BigInt::zero(), BigInt::from(-3), big.clone(), -&big] {
+        for b in [0, 1, u64::from(u32::MAX) + 1, u64::MAX] {
+            for acc in [BigInt::zero(), &a * b, -&a * b, &a * b + 1u32, -&big] {
+                let mut x = acc.clone();
+                x.add_mul_digit(&a, b);
+                assert_eq!(x, &acc + &a * b);
+                let mut x = acc.clone();
+                x.sub_mul_digit(&a, b);
+                assert_eq!(x, &acc - &a * b);
+            }
+        }
+    }
+}
+
 #[test]
 fn test_div_mod_floor() {
     fn check_sub(a: &BigInt, b: &BigInt, ans_d: &BigInt, ans_m: &BigInt) {
@@ -1193,6 +1360,200 @@ fn test_abs_sub() {
     assert_eq!(one.abs_sub(&-&one), two);
 }
 
+#[test]
+fn test_wrapping_bits() {
+    let values = [
+        0i128,
+        1,
+        -1,
+        0x7f,
+        -0x80,
+        0x80,
+        -0x81,
+        0x1234_5678,
+        i64::MIN.into(),
+        i128::MAX,
+        i128::MIN,
+    ];
+    for &a in &values {
+        let x = BigInt::from(a);
+        assert_eq!(x.wrapping_to_bits(8), BigInt::from(a as i8));
+        assert_eq!(x.wrapping_to_bits(16), BigInt::from(a as i16));
+        assert_eq!(x.wrapping_to_bits(64), BigInt::from(a as i64));
+        assert_eq!(x.wrapping_to_bits(128), BigInt::from(a));
+        assert_eq!(x.to_unsigned_bits(8), BigUint::from(a as u8));
+        assert_eq!(x.to_unsigned_bits(128), BigUint::from(a as u128));
+        assert_eq!(x.wrapping_to_bits(0), BigInt::zero());
+
+        for &b in &values {
+            let y = BigInt::from(b);
+            assert_eq!(
+                x.wrapping_add_bits(&y, 128),
+                BigInt::from(a.wrapping_add(b))
+            );
+            assert_eq!(
+                x.wrapping_sub_bits(&y, 128),
+                BigInt::from(a.wrapping_sub(b))
+            );
+            assert_eq!(
+                x.wrapping_mul_bits(&y, 128),
+                BigInt::from(a.wrapping_mul(b))
+            );
+            assert_eq!(
+                x.wrapping_mul_bits(&y, 32),
+                BigInt::from(a.wrapping_mul(b) as i32)
+            );
+        }
+    }
+
+    // A 1024-bit register
+    let min = -(BigInt::one() << 1023u32);
+    let max = -&min - 1u32;
+    assert_eq!(max.wrapping_add_bits(&BigInt::one(), 1024), min);
+    assert_eq!(min.wrapping_sub_bits(&BigInt::one(), 1024), max);
+    assert_eq!(min.wrapping_mul_bits(&BigInt::from(-1), 1024), min);
+    assert_eq!((&max * 3u32).wrapping_to_bits(1024), &max - 2u32);
+}
+
+#[test]
+fn test_bit_ranges() {
+    let mask = |range: &std::ops::Range<u64>| -> i128 {
+        (range.start..range.end).fold(0, |m, bit| m | 1 << bit)
+    };
+    let values = [
+        0i128,
+        1,
+        -1,
+        0xff,
+        -0x100,
+        0x1234_5678_9abc_def0,
+        -0x1234_5678_9abc_def0,
+        i128::MIN,
+        i128::MAX,
+    ];
+    let f = 0x25a5_a5a5_a5a5_a5a5_a5a5_a5a5_a5a5_a5a5i128;
+    let field = BigUint::from(f as u128);
+    for &n in &values {
+        let i = BigInt::from(n);
+        for start in (0..127).step_by(5) {
+            for end in (start..128).step_by(9) {
+                let range = start..end;
+                let m = mask(&range);
+                let extracted = ((n & m) as u128) >> start;
+                assert_eq!(i.extract_bits(range.clone()), BigUint::from(extracted));
+
+                let mut x = i.clone();
+                x.clear_bits(range.clone());
+                assert_eq!(x, BigInt::from(n & !m), "clear {} {:?}", n, range);
+
+                let mut x = i.clone();
+                x.set_bits(range.clone());
+                assert_eq!(x, BigInt::from(n | m), "set {} {:?}", n, range);
+
+                let mut x = i.clone();
+                x.deposit_bits(range.clone(), &field);
+                assert_eq!(
+                    x,
+                    BigInt::from(n & !m | (f << start) & m),
+                    "deposit {} {:?}",
+                    n,
+                    range
+                );
+            }
+        }
+    }
+
+    // Ranges past the end of the number
+    let mut x = BigInt::from(-2);
+    assert_eq!(x.extract_bits(200..208), BigUint::from(0xffu32));
+    x.set_bits(100..200);
+    assert_eq!(x, BigInt::from(-2));
+    x.clear_bits(0..1);
+    assert_eq!(x, BigInt::from(-2));
+    x.clear_bits(300..301);
+    assert_eq!(x, BigInt::from(-2) - (BigInt::one() << 300u32));
+    x.deposit_bits(0..400, &BigUint::from(5u32));
+    assert_eq!(x, BigInt::from(5) - (BigInt::one() << 400u32));
+
+    // Sign bits far past the end shouldn't cost anything to read or write.
+    let far = 1u64 << 40;
+    let mut x = BigInt::from(-1);
+    x.set_bits(0..far);
+    assert_eq!(x, BigInt::from(-1));
+    assert_eq!(x.extract_bits(far..far + 8), BigUint::from(0xffu32));
+    x.deposit_bits(far..far + 8, &BigUint::from(0xffu32));
+    assert_eq!(x, BigInt::from(-1));
+    x.clear_bits(0..4);
+    x.set_bits(2..far);
+    assert_eq!(x, BigInt::from(-4));
+
+    let x = -(BigInt::one() << 100u32);
+    assert_eq!(x.extract_bits(96..104), BigUint::from(0xf0u32));
+    assert_eq!(x.extract_bits(far..far + 4), BigUint::from(0xfu32));
+    assert_eq!(x.extract_bits(0..100), BigUint::ZERO);
+}
+
+#[test]
+fn test_count_ones_in() {
+    let values = [
+        0i128,
+        1,
+        -1,
+        0xff,
+        -0x100,
+        0x1234_5678_9abc_def0,
+        -0x1234_5678_9abc_def0,
+        i128::MIN,
+        i128::MAX,
+    ];
+    for &n in &values {
+        let i = BigInt::from(n);
+        for width in 0..=128 {
+            let mask = if width == 128 {
+                !0
+            } else {
+                (1u128 << width) - 1
+            };
+            assert_eq!(
+                i.count_ones_in(width),
+                u64::from((n as u128 & mask).count_ones())
+            );
+        }
+        let sign = if n < 0 { 1000 - 128 } else { 0 };
+        assert_eq!(i.count_ones_in(1000), u64::from(n.count_ones()) + sign);
+    }
+}
+
+#[test]
+fn test_capacity() {
+    let mut i = BigInt::with_capacity_bits(1000);
+    assert!(i.is_zero());
+    assert!(i.capacity_bits() >= 1000);
+
+    i -= BigInt::one() << 900u32;
+    let capacity = i.capacity_bits();
+    i.clear();
+    assert!(i.is_zero());
+    assert_eq!(i.sign(), NoSign);
+    assert_eq!(i.capacity_bits(), capacity);
+
+    i.reserve_bits(5000);
+    let capacity = i.capacity_bits();
+    assert!(capacity >= 5000);
+    i -= 5;
+    assert_eq!(i, BigInt::from(-5));
+    assert_eq!(i.capacity_bits(), capacity);
+    i += BigInt::from(5);
+    assert!(i.is_zero());
+    i -= BigInt::one() << 4000u32;
+    i >>= 3990;
+    i /= 3u32;
+    assert_eq!(i, BigInt::from(-341));
+    assert_eq!(i.capacity_bits(), capacity);
+    i.shrink_to_fit();
+    assert!(i.capacity_bits() < 5000);
+}
+
 #[test]
 fn test_from_str_radix() {
     fn check(s: &str, ans: Option<isize>) {
@@ -1223,6 +1584,116 @@ fn test_from_str_radix() {
     let _y = x.to_string();
 }
 
+#[test]
+fn test_from_str_radix_errors() {
+    fn check(s: &str, radix: u32, kind: BigIntErrorKind, position: Option<usize>) {
+        let err = BigInt::from_str_radix(s, radix).unwrap_err();
+        assert_eq!((err.kind(), err.position()), (kind, position), "{:?}", s);
+    }
+
+    check("", 10, BigIntErrorKind::Empty, None);
+    check("-", 10, BigIntErrorKind::SignOnly, None);
+    check("+", 10, BigIntErrorKind::SignOnly, None);
+    check("-_1", 10, BigIntErrorKind::MisplacedUnderscore, Some(1));
+    check("--7", 10, BigIntErrorKind::InvalidDigit, Some(1));
+    check("+-9", 10, BigIntErrorKind::InvalidDigit, Some(1));
+    check("-+3", 10, BigIntErrorKind::InvalidDigit, Some(0));
+    check("-123_456_7x9", 10, BigIntErrorKind::InvalidDigit, Some(10));
+    check("-ff", 0, BigIntErrorKind::InvalidRadix, None);
+
+    let err = "1.5".parse::<BigInt>().unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::InvalidDigit);
+    assert_eq!(err.position(), Some(1));
+    assert_eq!(err.to_string(), "invalid digit found in string");
+}
+
+#[test]
+fn test_from_str_radix_max_bits() {
+    let n = BigInt::from_str_radix_max_bits("-ffff_ffff", 16, 32).unwrap();
+    assert_eq!(n, BigInt::from(-i64::from(u32::MAX)));
+
+    let err = BigInt::from_str_radix_max_bits("-1_0000_0000", 16, 32).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+    let err = BigInt::from_str_radix_max_bits("-1", 10, 0).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+    let err = BigInt::from_str_radix_max_bits("-", 10, 64).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::SignOnly);
+}
+
+#[test]
+fn test_parse_literal() {
+    let ok = |s: &str, n: i128| assert_eq!(BigInt::parse_literal(s), Ok(BigInt::from(n)), "{}", s);
+    ok("0", 0);
+    ok("-0", 0);
+    ok("+42", 42);
+    ok("-1_000_000", -1_000_000);
+    ok("0b1010_1010", 0b1010_1010);
+    ok("-0o755", -0o755);
+    ok("0x_DEAD_beef_", 0xdead_beef);
+    ok(
+        "-0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff",
+        -0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+    );
+
+    let err =
+        |s: &str, options: &LiteralOptions, kind: BigIntErrorKind, position: Option<usize>| {
+            let e = BigInt::parse_literal_with(s, options).unwrap_err();
+            assert_eq!((e.kind(), e.position()), (kind, position), "{:?}", s);
+        };
+    let default = LiteralOptions::new();
+    err("", &default, BigIntErrorKind::Empty, None);
+    err("-", &default, BigIntErrorKind::SignOnly, None);
+    err("0x", &default, BigIntErrorKind::Empty, None);
+    err(
+        "-_1",
+        &default,
+        BigIntErrorKind::MisplacedUnderscore,
+        Some(1),
+    );
+    err("--1", &default, BigIntErrorKind::InvalidDigit, Some(1));
+    err("0x+1", &default, BigIntErrorKind::InvalidDigit, Some(2));
+    err("-0b102", &default, BigIntErrorKind::InvalidDigit, Some(5));
+    err("0X10", &default, BigIntErrorKind::InvalidDigit, Some(1));
+    err("10u8", &default, BigIntErrorKind::InvalidDigit, Some(2));
+
+    let strict = LiteralOptions::strict();
+    assert_eq!(
+        BigInt::parse_literal_with("-0x10", &strict),
+        Ok(BigInt::from(-16))
+    );
+    err(
+        "1_000",
+        &strict,
+        BigIntErrorKind::MisplacedUnderscore,
+        Some(1),
+    );
+    err(
+        "0x_10",
+        &strict,
+        BigIntErrorKind::MisplacedUnderscore,
+        Some(2),
+    );
+
+    let suffixed = LiteralOptions::new().type_suffix(true);
+    assert_eq!(
+        BigInt::parse_literal_with("-5i8", &suffixed),
+        Ok(BigInt::from(-5))
+    );
+    assert_eq!(
+        BigInt::parse_literal_with("0xffu128", &suffixed),
+        Ok(BigInt::from(255))
+    );
+    assert_eq!(
+        BigInt::parse_literal_with("1_usize", &suffixed),
+        Ok(BigInt::from(1))
+    );
+    err("u8", &suffixed, BigIntErrorKind::Empty, None);
+    err("1u7", &suffixed, BigIntErrorKind::InvalidDigit, Some(1));
+
+    let decimal = LiteralOptions::new().prefixes(false);
+    err("0x10", &decimal, BigIntErrorKind::InvalidDigit, Some(1));
+}
+
 #[test]
 fn test_lower_hex() {
     let a = BigInt::parse_bytes(b"A", 16).unwrap();
@@ -1276,6 +1747,48 @@ fn test_display() {
     assert_eq!(format!("{:♥>+#8}", a), "♥♥♥♥♥+10");
 }
 
+#[test]
+fn test_format_builder() {
+    let a = BigInt::from(-1234567);
+    assert_eq!(a.format().to_string(), "-1234567");
+    assert_eq!(a.format().group(3, ',').to_string(), "-1,234,567");
+    assert_eq!(
+        a.format().radix(16).prefix(true).min_digits(8).to_string(),
+        "-0x0012d687"
+    );
+    assert_eq!((-&a).format().plus_sign(true).to_string(), "+1234567");
+    assert_eq!(BigInt::zero().format().plus_sign(true).to_string(), "+0");
+    assert_eq!(format!("{:>12}", a.format().group(3, '_')), "  -1_234_567");
+}
+
+#[test]
+fn test_write_radix_to() {
+    let values = [
+        BigInt::zero(),
+        BigInt::from(-1),
+        BigInt::from(i64::MIN),
+        -BigInt::from(7).pow(1000u32),
+    ];
+    for n in &values {
+        for radix in 2..=36 {
+            let mut s = String::new();
+            n.write_radix_to(&mut s, radix).unwrap();
+            assert_eq!(s, n.to_str_radix(radix));
+
+            #[cfg(feature = "std")]
+            {
+                let mut v = Vec::new();
+                n.write_radix_to_io(&mut v, radix).unwrap();
+                assert_eq!(v, n.to_str_radix(radix).into_bytes());
+            }
+        }
+        assert_eq!(format!("{}", n), n.to_str_radix(10));
+        let sign = if n.is_negative() { "-" } else { "" };
+        let binary = format!("{}0b{}", sign, n.magnitude().to_str_radix(2));
+        assert_eq!(format!("{:#b}", n), binary);
+    }
+}
+
 #[test]
 fn test_neg() {
     assert!(-BigInt::new(Plus, vec![1, 1, 1]) == BigInt::new(Minus, vec![1, 1, 1]));
@@ -1381,6 +1894,20 @@ fn test_pow() {
     assert!(manual_1e10000 == pow_1e10000);
 }
 
+#[test]
+fn test_try_alloc_ops() {
+    let n = BigInt::from(-10);
+    assert_eq!(n.try_pow(101).unwrap(), Pow::pow(&n, 101u32));
+    assert_eq!(n.try_pow(100).unwrap(), Pow::pow(&n, 100u32));
+
+    let big = Pow::pow(&n, 51u32);
+    assert_eq!(big.try_mul(&big).unwrap(), &big * &big);
+    assert_eq!(big.try_mul(&-&big).unwrap(), -(&big * &big));
+    assert_eq!(big.try_shl(100).unwrap(), &big << 100u32);
+
+    assert!(n.try_shl(u64::MAX).is_err());
+}
+
 #[test]
 fn test_bit() {
     // 12 = (1100)_2
@@ -1473,3 +2000,27 @@ fn test_set_bit() {
     x.set_bit(0, false);
     assert_eq!(x, BigInt::from_biguint(Minus, BigUint::one() << 200));
 }
+
+#[test]
+fn test_const() {
+    const MIN: BigInt = BigInt::from_i64_const(i64::MIN);
+    const WIDE: BigInt = BigInt::from_i128_const(i128::MIN + 1);
+    static HUGE: BigInt = bigint!("-0x1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001");
+    assert_eq!(MIN, BigInt::from(i64::MIN));
+    assert_eq!(WIDE, BigInt::from(i128::MIN + 1));
+    assert_eq!(BigInt::from_i128_const(0).sign(), NoSign);
+    assert_eq!(BigInt::from_i64_const(1).sign(), Plus);
+    assert_eq!(HUGE, -(BigInt::one() << 160u32) - 1);
+    assert_eq!(HUGE.sign(), Minus);
+
+    assert_eq!(bigint!("-0").sign(), NoSign);
+    assert_eq!(bigint!("+0o17"), BigInt::from(15));
+    assert_eq!(
+        bigint!("-340282366920938463463374607431768211456"),
+        BigInt::parse_literal("-340282366920938463463374607431768211456").unwrap()
+    );
+
+    let mut n = HUGE.clone();
+    n += 2;
+    assert_eq!(n, -(BigInt::one() << 160u32) + 1);
+}
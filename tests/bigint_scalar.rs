@@ -155,3 +155,42 @@ fn test_scalar_div_rem_zero() {
     catch_unwind(|| BigInt::one() / 0u32).unwrap_err();
     catch_unwind(|| BigInt::one() % 0u32).unwrap_err();
 }
+
+#[test]
+fn test_scalar_bitwise() {
+    fn check(x: &BigInt, y: &BigInt) {
+        let (and, or, xor) = (x & y, x | y, x ^ y);
+        let (x, y) = (x.clone(), y.clone());
+        assert_signed_scalar_op!(x & y == and);
+        assert_signed_scalar_assign_op!(x &= y == and);
+        assert_signed_scalar_op!(x | y == or);
+        assert_signed_scalar_assign_op!(x |= y == or);
+        assert_signed_scalar_op!(x ^ y == xor);
+        assert_signed_scalar_assign_op!(x ^= y == xor);
+    }
+
+    let mut values: Vec<BigInt> = [
+        0i128,
+        1,
+        0xff,
+        0x100,
+        0xffff_ffff,
+        0x1_0000_0000,
+        0x1234_5678_9abc_def0,
+        i64::MIN as i128,
+        i64::MAX as i128,
+        i128::MAX,
+    ]
+    .iter()
+    .map(|&n| BigInt::from(n))
+    .chain([(BigInt::one() << 200u32) - 1u32, BigInt::one() << 128u32])
+    .collect();
+    values.extend(values.clone().into_iter().map(Neg::neg));
+    values.push(BigInt::from(i128::MIN));
+
+    for a in &values {
+        for b in &values {
+            check(a, b);
+        }
+    }
+}
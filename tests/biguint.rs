@@ -1,6 +1,6 @@
 use num_bigint::Sign::Plus;
-use num_bigint::{BigInt, ToBigInt};
-use num_bigint::{BigUint, ToBigUint};
+use num_bigint::{biguint, BigUint, BigUintParser, BigUintRef, ToBigUint};
+use num_bigint::{BigInt, BigIntErrorKind, ToBigInt};
 use num_integer::Integer;
 
 use std::cmp::Ordering::{Equal, Greater, Less};
@@ -87,6 +87,32 @@ fn test_to_bytes_le() {
     assert_eq!(b.to_bytes_le(), [0, 2, 0, 0, 0, 0, 0, 0, 1]);
 }
 
+#[test]
+fn test_write_bytes() {
+    for &n in &[0, 1, 0xff, 0x100, 0x1234_5678_9abc_def0_1234, u128::MAX] {
+        let u = BigUint::from(n);
+        let min = (u.bits() as usize + 7) / 8;
+        for width in 0..=20 {
+            let mut be = vec![0xaa; width];
+            let mut le = vec![0xaa; width];
+            if width < min {
+                assert!(u.write_bytes_be(&mut be).is_err());
+                assert!(u.write_bytes_le(&mut le).is_err());
+                assert!(be.iter().chain(&le).all(|&b| b == 0xaa));
+                continue;
+            }
+            u.write_bytes_be(&mut be).unwrap();
+            u.write_bytes_le(&mut le).unwrap();
+            let expected: Vec<u8> = (0..width)
+                .map(|k| n.to_le_bytes().get(k).copied().unwrap_or(0))
+                .collect();
+            assert_eq!(le, expected);
+            be.reverse();
+            assert_eq!(be, expected);
+        }
+    }
+}
+
 #[test]
 fn test_cmp() {
     let data: [&[_]; 7] = [&[], &[1], &[2], &[!0], &[0, 1], &[2, 1], &[1, 1, 1]];
@@ -922,6 +948,147 @@ fn test_div_rem_big_multiple() {
     assert_eq!(rem, &a - 1u32);
 }
 
+#[test]
+fn test_view() {
+    let values = [
+        BigUint::zero(),
+        BigUint::one(),
+        BigUint::from(u32::MAX),
+        BigUint::from(u64::MAX),
+        BigUint::from(u64::MAX) + 1u32,
+        BigUint::from(3u32).pow(100u32),
+        BigUint::from(7u32).pow(1000u32),
+    ];
+    for a in &values {
+        let mut u32_digits = a.to_u32_digits();
+        u32_digits.push(0);
+        let mut u64_digits = a.to_u64_digits();
+        u64_digits.extend([0, 0]);
+        let views = [
+            BigUintRef::from(a),
+            BigUintRef::from_u32_slice(&u32_digits),
+            BigUintRef::from_u64_slice(&u64_digits),
+        ];
+        for view in &views {
+            assert_eq!(*view, *a);
+            assert_eq!(view.to_biguint(), *a);
+            assert_eq!(view.is_zero(), a.is_zero());
+            assert_eq!(view.bits(), a.bits());
+            assert_eq!(view.to_u64(), a.to_u64());
+            assert_eq!(view.to_i128(), a.to_i128());
+            assert_eq!(view.to_f32(), a.to_f32());
+            assert_eq!(view.to_f64(), a.to_f64());
+            assert_eq!(view.to_string(), a.to_string());
+            assert_eq!(format!("{:#x}", view), format!("{:#x}", a));
+        }
+
+        for b in &values {
+            let b_u32 = b.to_u32_digits();
+            let b_u64 = b.to_u64_digits();
+            let others = [
+                BigUintRef::from_u32_slice(&b_u32),
+                BigUintRef::from_u64_slice(&b_u64),
+            ];
+            for (view, other) in views.iter().zip(&others) {
+                assert_eq!(view.cmp(other), a.cmp(b));
+                assert_eq!(view.partial_cmp(b), a.partial_cmp(b));
+                assert_eq!(b.partial_cmp(view), b.partial_cmp(a));
+                assert_eq!(view == other, a == b);
+
+                assert_eq!(view + other, a + b);
+                assert_eq!(view * b, a * b);
+                assert_eq!(b * view, a * b);
+                if a >= b {
+                    assert_eq!(view - b, a - b);
+                }
+                if !b.is_zero() {
+                    assert_eq!(view / other, a / b);
+                    assert_eq!(view % b, a % b);
+                    assert_eq!(&(a * b) / other, a.clone());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_into_ops() {
+    // Start from dirty buffers, which must be fully overwritten.
+    let mut out = BigUint::from(7u32).pow(200u32);
+    let (mut q, mut r) = (out.clone(), out.clone());
+    let cap = out.capacity_bits();
+    let qr_cap = q.capacity_bits();
+
+    for elm in SUM_TRIPLES.iter() {
+        let (a_vec, b_vec, c_vec) = *elm;
+        let a = BigUint::from_slice(a_vec);
+        let b = BigUint::from_slice(b_vec);
+        let c = BigUint::from_slice(c_vec);
+
+        BigUint::add_into(&mut out, &a, &b);
+        assert_eq!(out, c);
+        BigUint::add_into(&mut out, &b, &a);
+        assert_eq!(out, c);
+        BigUint::sub_into(&mut out, &c, &a);
+        assert_eq!(out, b);
+        assert_eq!(out.capacity_bits(), cap);
+    }
+
+    for elm in MUL_TRIPLES.iter() {
+        let (a_vec, b_vec, c_vec) = *elm;
+        let a = BigUint::from_slice(a_vec);
+        let b = BigUint::from_slice(b_vec);
+        let c = BigUint::from_slice(c_vec);
+
+        BigUint::mul_into(&mut out, &a, &b);
+        assert_eq!(out, c);
+        assert_eq!(out.capacity_bits(), cap);
+    }
+
+    for elm in DIV_REM_QUADRUPLES.iter() {
+        let (a_vec, b_vec, c_vec, d_vec) = *elm;
+        let a = BigUint::from_slice(a_vec);
+        let b = BigUint::from_slice(b_vec);
+        let c = BigUint::from_slice(c_vec);
+        let d = BigUint::from_slice(d_vec);
+
+        if !b.is_zero() {
+            BigUint::div_rem_into(&mut q, &mut r, &a, &b);
+            assert_eq!((&q, &r), (&c, &d));
+            assert_eq!(q.capacity_bits(), qr_cap);
+            assert_eq!(r.capacity_bits(), qr_cap);
+        }
+    }
+
+    let a = BigUint::from(3u32).pow(300u32);
+    let b = BigUint::from(5u32).pow(100u32);
+    BigUint::div_rem_into(&mut q, &mut r, &a, &b);
+    assert_eq!((&q, &r), (&(&a / &b), &(&a % &b)));
+    BigUint::div_rem_into(&mut q, &mut r, &b, &a);
+    assert_eq!((&q, &r), (&BigUint::zero(), &b));
+    BigUint::div_rem_into(&mut q, &mut r, &a, &a);
+    assert_eq!((&q, &r), (&BigUint::one(), &BigUint::zero()));
+
+    // Small results keep the room of large buffers, on both division paths.
+    let mut q = BigUint::with_capacity_bits(10000);
+    let mut r = BigUint::with_capacity_bits(10000);
+    let cap = q.capacity_bits();
+    let a = BigUint::from(3u32).pow(4000u32);
+    for b in [BigUint::from(7u32).pow(100u32), BigUint::from(7u32)] {
+        BigUint::div_rem_into(&mut q, &mut r, &a, &b);
+        assert_eq!((&q, &r), (&(&a / &b), &(&a % &b)));
+        assert_eq!(q.capacity_bits(), cap);
+        assert_eq!(r.capacity_bits(), cap);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_sub_into_fail_on_underflow() {
+    let mut out = BigUint::zero();
+    BigUint::sub_into(&mut out, &BigUint::zero(), &BigUint::one());
+}
+
 #[test]
 fn test_div_ceil() {
     fn check(a: &BigUint, b: &BigUint, d: &BigUint, m: &BigUint) {
@@ -1657,6 +1824,125 @@ fn test_from_str_radix() {
     assert_eq!(ff, Some(BigUint::from_slice(&[0xff])));
 }
 
+#[test]
+fn test_from_str_radix_errors() {
+    fn check(s: &str, radix: u32, kind: BigIntErrorKind, position: Option<usize>) {
+        let err = BigUint::from_str_radix(s, radix).unwrap_err();
+        assert_eq!((err.kind(), err.position()), (kind, position), "{:?}", s);
+    }
+
+    check("", 10, BigIntErrorKind::Empty, None);
+    check("+", 10, BigIntErrorKind::SignOnly, None);
+    check("_", 2, BigIntErrorKind::MisplacedUnderscore, Some(0));
+    check("+_1", 2, BigIntErrorKind::MisplacedUnderscore, Some(1));
+    check("Z", 10, BigIntErrorKind::InvalidDigit, Some(0));
+    check("++1", 10, BigIntErrorKind::InvalidDigit, Some(0));
+    check("-1", 10, BigIntErrorKind::InvalidDigit, Some(0));
+    check("0+2", 10, BigIntErrorKind::InvalidDigit, Some(1));
+    check("+1_000_0a0", 10, BigIntErrorKind::InvalidDigit, Some(8));
+    check("1012", 2, BigIntErrorKind::InvalidDigit, Some(3));
+    check("12", 1, BigIntErrorKind::InvalidRadix, None);
+    check("12", 37, BigIntErrorKind::InvalidRadix, None);
+}
+
+#[test]
+fn test_from_str_radix_max_bits() {
+    let n = BigUint::from_str_radix_max_bits("ffff_ffff", 16, 32).unwrap();
+    assert_eq!(n, BigUint::from(u32::MAX));
+    let n = BigUint::from_str_radix_max_bits("0000000000000000000001", 2, 1).unwrap();
+    assert_eq!(n, BigUint::one());
+
+    let err = BigUint::from_str_radix_max_bits("1_0000_0000", 16, 32).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+    let err = BigUint::from_str_radix_max_bits("1z", 36, 0).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+
+    // Oversized input is rejected before any conversion work.
+    let huge = "9".repeat(1_000_000);
+    let err = BigUint::from_str_radix_max_bits(&huge, 10, 64).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::TooLong);
+
+    let err = BigUint::from_str_radix_max_bits("12z", 10, 64).unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::InvalidDigit);
+}
+
+#[test]
+fn test_parse_literal() {
+    assert_eq!(BigUint::parse_literal("+0x_ff"), Ok(BigUint::from(255u32)));
+    assert_eq!(BigUint::parse_literal("0o1_7"), Ok(BigUint::from(15u32)));
+    assert_eq!(BigUint::parse_literal("1_000"), Ok(BigUint::from(1000u32)));
+
+    let err = BigUint::parse_literal("-1").unwrap_err();
+    assert_eq!(
+        (err.kind(), err.position()),
+        (BigIntErrorKind::InvalidDigit, Some(0))
+    );
+    let err = BigUint::parse_literal("0b12").unwrap_err();
+    assert_eq!(
+        (err.kind(), err.position()),
+        (BigIntErrorKind::InvalidDigit, Some(3))
+    );
+}
+
+#[test]
+fn test_parser() {
+    let n = BigUint::new((1..20).collect());
+    for radix in 2..37 {
+        let s = n.to_str_radix(radix);
+        for step in [1, 3, 7, 64, s.len()] {
+            let mut parser = BigUintParser::new(radix);
+            for piece in s.as_bytes().chunks(step) {
+                parser.push(piece).unwrap();
+            }
+            assert_eq!(parser.finish().unwrap(), n, "radix {} step {}", radix, step);
+        }
+
+        let mut parser = BigUintParser::new(radix);
+        parser.push(b"+0").unwrap();
+        parser.push(b"00_1").unwrap();
+        assert_eq!(parser.finish().unwrap(), BigUint::from(1u32));
+    }
+
+    let mut parser = BigUintParser::new(2);
+    parser.push(b"+").unwrap();
+    parser.push(b"").unwrap();
+    let err = parser.finish().unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::SignOnly);
+
+    let err = BigUintParser::new(10).finish().unwrap_err();
+    assert_eq!(err.kind(), BigIntErrorKind::Empty);
+
+    let mut parser = BigUintParser::new(10);
+    parser.push(b"+").unwrap();
+    let err = parser.push(b"_1").unwrap_err();
+    assert_eq!(
+        (err.kind(), err.position()),
+        (BigIntErrorKind::MisplacedUnderscore, Some(1))
+    );
+
+    let mut parser = BigUintParser::new(8);
+    parser.push(b"1234").unwrap();
+    let err = parser.push(b"5678").unwrap_err();
+    assert_eq!(
+        (err.kind(), err.position()),
+        (BigIntErrorKind::InvalidDigit, Some(7))
+    );
+    assert_eq!(parser.push(b"0"), Err(err.clone()));
+    assert_eq!(parser.finish(), Err(err));
+
+    #[cfg(feature = "std")]
+    {
+        let mut parser = BigUintParser::new(16);
+        parser.push(b"dead").unwrap();
+        parser.read_from(&b"beef"[..]).unwrap();
+        assert_eq!(parser.finish().unwrap(), BigUint::from(0xdead_beef_u32));
+
+        let mut parser = BigUintParser::new(16);
+        let err = parser.read_from(&b"xyz"[..]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
 #[test]
 fn test_all_str_radix() {
     let n = BigUint::new((0..10).collect());
@@ -1734,6 +2020,353 @@ fn test_display() {
     assert_eq!(format!("{:♥>+#8}", a), "♥♥♥♥♥+10");
 }
 
+#[test]
+fn test_format_builder() {
+    let a = BigUint::parse_bytes(b"A", 16).unwrap();
+    let hello = BigUint::parse_bytes(b"22405534230753963835153736737", 10).unwrap();
+
+    assert_eq!(a.format().to_string(), "10");
+    assert_eq!(a.format().plus_sign(true).to_string(), "+10");
+    assert_eq!(BigUint::zero().format().group(3, ',').to_string(), "0");
+    assert_eq!(
+        hello.format().group(3, ',').to_string(),
+        "22,405,534,230,753,963,835,153,736,737"
+    );
+    assert_eq!(
+        hello
+            .format()
+            .radix(16)
+            .group(4, '_')
+            .prefix(true)
+            .to_string(),
+        "0x4865_6c6c_6f20_776f_726c_6421"
+    );
+    assert_eq!(
+        hello.format().radix(16).uppercase(true).to_string(),
+        "48656C6C6F20776F726C6421"
+    );
+    assert_eq!(a.format().radix(2).min_digits(8).to_string(), "00001010");
+    assert_eq!(
+        a.format().radix(2).min_digits(8).group(4, ' ').to_string(),
+        "0000 1010"
+    );
+    assert_eq!(a.format().radix(8).prefix(true).to_string(), "0o12");
+    assert_eq!(a.format().radix(36).prefix(true).to_string(), "a");
+    assert_eq!(
+        hello.format().group(3, '\u{2009}').to_string(),
+        "22\u{2009}405\u{2009}534\u{2009}230\u{2009}753\u{2009}963\u{2009}835\u{2009}153\u{2009}736\u{2009}737"
+    );
+    assert_eq!(format!("{:♥>8}", a.format().group(1, ',')), "♥♥♥♥♥1,0");
+
+    // long enough to flush the internal buffer several times
+    let big = BigUint::from(7u32).pow(1000u32);
+    let grouped = big.format().group(3, ',').to_string();
+    assert_eq!(grouped.replace(',', ""), big.to_string());
+    assert!(grouped.split(',').skip(1).all(|g| g.len() == 3));
+}
+
+#[test]
+fn test_write_radix_to() {
+    let values = [
+        BigUint::zero(),
+        BigUint::from(1u32),
+        BigUint::from(u64::MAX),
+        BigUint::from(10u32).pow(19u32),
+        BigUint::from(3u32).pow(2000u32),
+        (BigUint::one() << 4099) - 1u32,
+    ];
+    for n in &values {
+        for radix in 2..=36 {
+            let mut s = String::new();
+            n.write_radix_to(&mut s, radix).unwrap();
+            assert_eq!(s, n.to_str_radix(radix));
+
+            #[cfg(feature = "std")]
+            {
+                let mut v = Vec::new();
+                n.write_radix_to_io(&mut v, radix).unwrap();
+                assert_eq!(v, n.to_str_radix(radix).into_bytes());
+            }
+        }
+        assert_eq!(format!("{}", n), n.to_str_radix(10));
+        assert_eq!(format!("{:+#x}", n), format!("+0x{}", n.to_str_radix(16)));
+        assert_eq!(format!("{:X}", n), n.to_str_radix(16).to_ascii_uppercase());
+    }
+}
+
+#[test]
+fn test_wrapping_bits() {
+    let values = [
+        0u128,
+        1,
+        0x7f,
+        0x80,
+        0xff,
+        0x1234_5678,
+        u64::MAX.into(),
+        u128::MAX,
+    ];
+    for &a in &values {
+        for &b in &values {
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            for (bits, mask) in [(8, 0xff), (64, u64::MAX.into()), (128, u128::MAX)] {
+                let check = |result: BigUint, expected: u128| {
+                    assert_eq!(result, BigUint::from(expected & mask))
+                };
+                check(x.wrapping_add_bits(&y, bits), a.wrapping_add(b));
+                check(x.wrapping_sub_bits(&y, bits), a.wrapping_sub(b));
+                check(x.wrapping_mul_bits(&y, bits), a.wrapping_mul(b));
+            }
+            assert_eq!(x.to_signed_from_bits(8), BigInt::from(a as i8));
+            assert_eq!(x.to_signed_from_bits(64), BigInt::from(a as i64));
+            assert_eq!(x.to_signed_from_bits(128), BigInt::from(a as i128));
+        }
+    }
+
+    // Compare a 257-bit register against the remainder with a power of two.
+    let modulus = BigUint::one() << 257u32;
+    let a = (&modulus >> 1u32) + BigUint::from(12345u32);
+    let b = &modulus - 1u32;
+    assert_eq!(a.wrapping_add_bits(&b, 257), (&a + &b) % &modulus);
+    assert_eq!(
+        a.wrapping_sub_bits(&b, 257),
+        (&a + &modulus - &b) % &modulus
+    );
+    assert_eq!(b.wrapping_sub_bits(&a, 257), (&b - &a) % &modulus);
+    assert_eq!(a.wrapping_mul_bits(&b, 257), (&a * &b) % &modulus);
+    assert_eq!(b.to_signed_from_bits(257), BigInt::from(-1));
+    assert_eq!(
+        a.to_signed_from_bits(257),
+        BigInt::from(a.clone()) - BigInt::from(modulus.clone())
+    );
+
+    let mut n = b.clone();
+    n.truncate_bits(300);
+    assert_eq!(n, b);
+    n.truncate_bits(1);
+    assert_eq!(n, BigUint::one());
+    n.truncate_bits(0);
+    assert!(n.is_zero());
+    assert_eq!(
+        BigUint::zero().wrapping_sub_bits(&BigUint::one(), 0),
+        BigUint::zero()
+    );
+}
+
+#[test]
+fn test_bit_ranges() {
+    let mask = |range: &std::ops::Range<u64>| -> u128 {
+        (range.start..range.end).fold(0, |m, bit| m | 1 << bit)
+    };
+    let values = [
+        0u128,
+        1,
+        0xff,
+        0x1234_5678_9abc_def0,
+        0x8000_0000_0000_0000_0000_0001,
+        u128::MAX >> 1,
+    ];
+    let field = BigUint::from(0xa5a5_a5a5_a5a5_a5a5_a5a5_a5a5_a5a5_a5a5u128);
+    for &n in &values {
+        let u = BigUint::from(n);
+        for start in (0..127).step_by(7) {
+            for end in (start..128).step_by(11) {
+                let range = start..end;
+                let m = mask(&range);
+                assert_eq!(
+                    u.extract_bits(range.clone()),
+                    BigUint::from((n & m) >> start)
+                );
+
+                let mut x = u.clone();
+                x.clear_bits(range.clone());
+                assert_eq!(x, BigUint::from(n & !m));
+
+                let mut x = u.clone();
+                x.set_bits(range.clone());
+                assert_eq!(x, BigUint::from(n | m));
+
+                let mut x = u.clone();
+                x.deposit_bits(range.clone(), &field);
+                let f = 0xa5a5_a5a5_a5a5_a5a5_a5a5_a5a5_a5a5_a5a5u128;
+                assert_eq!(x, BigUint::from(n & !m | (f << start) & m));
+            }
+        }
+    }
+
+    // Ranges past the end of the number
+    let mut x = BigUint::from(1u32);
+    assert!(x.extract_bits(200..300).is_zero());
+    x.clear_bits(200..300);
+    assert_eq!(x, BigUint::from(1u32));
+    x.set_bits(200..201);
+    assert_eq!(x, (BigUint::from(1u32) << 200u32) + 1u32);
+    x.deposit_bits(100..400, &BigUint::from(3u32));
+    assert_eq!(x, (BigUint::from(3u32) << 100u32) + 1u32);
+    x.deposit_bits(0..400, &BigUint::zero());
+    assert!(x.is_zero());
+}
+
+#[test]
+fn test_bit_scanning() {
+    let values = [
+        BigUint::zero(),
+        BigUint::one(),
+        BigUint::from(0x8000_0000u32),
+        BigUint::from(0xffff_ffff_ffff_ffffu64),
+        BigUint::from(0x1234_5678_9abc_def0_0000_0000_0000_0001u128),
+        (BigUint::one() << 200u32) + (BigUint::one() << 63u32),
+        (BigUint::one() << 256u32) - 1u32,
+    ];
+    for n in &values {
+        let bits = n.bits();
+        let ones: Vec<u64> = (0..bits).filter(|&i| n.bit(i)).collect();
+        let zeros: Vec<u64> = (0..bits).filter(|&i| !n.bit(i)).collect();
+        assert_eq!(n.iter_ones().collect::<Vec<_>>(), ones);
+        assert_eq!(n.iter_zeros().collect::<Vec<_>>(), zeros);
+        assert_eq!(n.iter_ones().count() as u64, n.count_ones());
+
+        for from in 0..bits + 70 {
+            let next = (from..bits).find(|&i| n.bit(i));
+            let prev = (0..=from).rev().find(|&i| n.bit(i));
+            assert_eq!(n.next_set_bit(from), next, "next {} {}", n, from);
+            assert_eq!(n.prev_set_bit(from), prev, "prev {} {}", n, from);
+
+            let width = from;
+            let set = (0..width).filter(|&i| n.bit(i)).count() as u64;
+            assert_eq!(n.count_zeros(width), width - set);
+            let top = (0..width).rev().find(|&i| n.bit(i)).map_or(0, |i| i + 1);
+            assert_eq!(n.leading_zeros_in(width), width - top);
+        }
+        assert_eq!(n.next_set_bit(u64::MAX), None);
+        assert_eq!(n.prev_set_bit(u64::MAX), bits.checked_sub(1));
+        assert_eq!(n.count_zeros(u64::MAX), u64::MAX - n.count_ones());
+    }
+}
+
+#[test]
+fn test_bits_in_width() {
+    let values = [
+        0u128,
+        1,
+        0x8001,
+        0x1234_5678_9abc_def0,
+        0xf000_0000_0000_0000_0000_0000_0000_000f,
+        u128::MAX,
+    ];
+    for &n in &values {
+        let u = BigUint::from(n);
+        assert_eq!(u.not_bits(128), BigUint::from(!n));
+        assert_eq!(u.reverse_bits(128), BigUint::from(n.reverse_bits()));
+        assert_eq!(u.swap_bytes(128), BigUint::from(n.swap_bytes()));
+        assert_eq!(u.not_bits(64), BigUint::from(!(n as u64)));
+        assert_eq!(u.reverse_bits(64), BigUint::from((n as u64).reverse_bits()));
+        assert_eq!(u.swap_bytes(64), BigUint::from((n as u64).swap_bytes()));
+        assert_eq!(u.reverse_bits(16), BigUint::from((n as u16).reverse_bits()));
+        assert_eq!(
+            u.swap_bytes(24),
+            BigUint::from((n as u32 & 0xff_ffff).swap_bytes() >> 8)
+        );
+        for k in [0, 1, 31, 32, 63, 64, 100, 127, 128, 200] {
+            assert_eq!(
+                u.rotate_left_bits(k, 128),
+                BigUint::from(n.rotate_left(k as u32))
+            );
+            assert_eq!(
+                u.rotate_right_bits(k, 128),
+                BigUint::from(n.rotate_right(k as u32))
+            );
+            let m = n as u16;
+            assert_eq!(
+                u.rotate_left_bits(k, 16),
+                BigUint::from(m.rotate_left(k as u32))
+            );
+            assert_eq!(
+                u.rotate_right_bits(k, 16),
+                BigUint::from(m.rotate_right(k as u32))
+            );
+        }
+    }
+
+    // Widths that aren't a whole number of digits, checked bit by bit
+    let n = (BigUint::one() << 300u32) - 12345u32;
+    for width in [0, 1, 7, 33, 100, 250, 301, 400] {
+        let not = n.not_bits(width);
+        let reversed = n.reverse_bits(width);
+        let rotated = n.rotate_left_bits(37, width);
+        for i in 0..width + 10 {
+            let inside = i < width;
+            assert_eq!(not.bit(i), inside && !n.bit(i));
+            assert_eq!(reversed.bit(i), inside && n.bit(width - 1 - i));
+            assert_eq!(
+                rotated.bit(i),
+                inside && n.bit((i + width - 37 % width) % width)
+            );
+        }
+        assert_eq!(
+            n.rotate_right_bits(37, width).rotate_left_bits(37, width),
+            {
+                let mut m = n.clone();
+                m.truncate_bits(width);
+                m
+            }
+        );
+    }
+    assert!(n.rotate_left_bits(5, 0).is_zero());
+}
+
+#[test]
+fn test_capacity() {
+    let mut n = BigUint::with_capacity_bits(1000);
+    assert!(n.is_zero());
+    let capacity = n.capacity_bits();
+    assert!(capacity >= 1000);
+
+    n.set_bit(999, true);
+    assert_eq!(n.capacity_bits(), capacity);
+    n.clear();
+    assert!(n.is_zero());
+    assert_eq!(n.capacity_bits(), capacity);
+
+    n.reserve_bits(5000);
+    assert!(n.capacity_bits() >= 5000);
+    n.reserve_bits(10);
+    assert!(n.capacity_bits() >= 5000);
+
+    let capacity = n.capacity_bits();
+    n += 12345u32;
+    assert_eq!(n, BigUint::from(12345u32));
+    assert_eq!(n.capacity_bits(), capacity);
+    n.shrink_to_fit();
+    assert!(n.capacity_bits() < 5000);
+
+    let mut m = BigUint::one() << 3000u32;
+    m.shrink_to_fit();
+    assert!(m.capacity_bits() >= 3001);
+    assert_eq!(m, BigUint::one() << 3000u32);
+}
+
+#[test]
+fn test_capacity_kept() {
+    let mut n = BigUint::with_capacity_bits(10000);
+    let capacity = n.capacity_bits();
+
+    n += 1u32;
+    n <<= 5000;
+    n -= 1u32;
+    n >>= 4990;
+    assert_eq!(n, BigUint::from(1023u32));
+    n /= 7u32;
+    assert_eq!(n, BigUint::from(146u32));
+    n <<= 9000;
+    n += &n.clone();
+    n >>= 9001;
+    assert_eq!(n, BigUint::from(146u32));
+    n -= 146u32;
+    assert!(n.is_zero());
+    assert_eq!(n.capacity_bits(), capacity);
+}
+
 #[test]
 fn test_factor() {
     fn factor(n: usize) -> BigUint {
@@ -1863,6 +2496,22 @@ fn test_pow() {
     assert!(manual_1e10000 == pow_1e10000);
 }
 
+#[test]
+fn test_try_alloc_ops() {
+    let n = BigUint::from(10u32);
+    assert_eq!(n.try_pow(10_000).unwrap(), Pow::pow(&n, 10_000u32));
+    assert_eq!(n.try_pow(0).unwrap(), BigUint::one());
+    assert_eq!(BigUint::zero().try_pow(u32::MAX).unwrap(), BigUint::zero());
+
+    let big = Pow::pow(&n, 100u32);
+    assert_eq!(big.try_mul(&big).unwrap(), &big * &big);
+    assert_eq!(big.try_mul(&BigUint::zero()).unwrap(), BigUint::zero());
+    assert_eq!(big.try_shl(100).unwrap(), &big << 100u32);
+    assert_eq!(BigUint::zero().try_shl(u64::MAX).unwrap(), BigUint::zero());
+
+    assert!(n.try_shl(u64::MAX).is_err());
+}
+
 #[test]
 fn test_trailing_zeros() {
     assert!(BigUint::from(0u8).trailing_zeros().is_none());
@@ -1919,3 +2568,50 @@ fn test_set_bit() {
     x.set_bit(1, false);
     assert_eq!(x, BigUint::zero());
 }
+
+#[test]
+fn test_const() {
+    const SMALL: BigUint = BigUint::from_u64_const(u64::MAX);
+    const WIDE: BigUint = BigUint::from_u128_const(u128::MAX);
+    static HUGE: BigUint = biguint!("0x1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001");
+    assert_eq!(SMALL, BigUint::from(u64::MAX));
+    assert_eq!(WIDE, BigUint::from(u128::MAX));
+    assert_eq!(BigUint::from_u64_const(0), BigUint::zero());
+    assert_eq!(HUGE, (BigUint::one() << 160u32) + 1u32);
+
+    let literals = [
+        "0",
+        "0b_1010",
+        "0o777",
+        "000340282366920938463463374607431768211456",
+        "1_000_000_000_000_000_000_000_000_000_000_000_000_000_000_000",
+        "+0xFFff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff",
+    ];
+    let values = [
+        biguint!("0"),
+        biguint!("0b_1010"),
+        biguint!("0o777"),
+        biguint!("000340282366920938463463374607431768211456"),
+        biguint!("1_000_000_000_000_000_000_000_000_000_000_000_000_000_000_000"),
+        biguint!("+0xFFff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff"),
+    ];
+    for (s, n) in literals.iter().zip(&values) {
+        let parsed = BigUint::parse_literal(s).unwrap();
+        assert_eq!(*n, parsed);
+        assert_eq!(n.to_string(), parsed.to_string());
+        assert_eq!(n.bits(), parsed.bits());
+    }
+
+    // Values borrowing static digits are copied when modified.
+    let mut n = HUGE.clone();
+    n += 1u32;
+    assert_eq!(n, (BigUint::one() << 160u32) + 2u32);
+    let mut n = HUGE.clone();
+    n >>= 64u32;
+    assert_eq!(n, BigUint::one() << 96u32);
+    let mut n = HUGE.clone();
+    n.shrink_to_fit();
+    n.set_zero();
+    assert!(n.is_zero());
+    assert_eq!(HUGE, (BigUint::one() << 160u32) + 1u32);
+}
@@ -121,3 +121,39 @@ fn test_scalar_div_rem_zero() {
     catch_unwind(|| BigUint::one() / 0u32).unwrap_err();
     catch_unwind(|| BigUint::one() % 0u32).unwrap_err();
 }
+
+#[test]
+fn test_scalar_bitwise() {
+    fn check(x: &BigUint, y: &BigUint) {
+        let (and, or, xor) = (x & y, x | y, x ^ y);
+        let (x, y) = (x.clone(), y.clone());
+        assert_unsigned_scalar_op!(x & y == and);
+        assert_unsigned_scalar_assign_op!(x &= y == and);
+        assert_unsigned_scalar_op!(x | y == or);
+        assert_unsigned_scalar_assign_op!(x |= y == or);
+        assert_unsigned_scalar_op!(x ^ y == xor);
+        assert_unsigned_scalar_assign_op!(x ^= y == xor);
+    }
+
+    let values: Vec<BigUint> = [
+        0u128,
+        1,
+        0xff,
+        0xffff_ffff,
+        0x1_0000_0000,
+        0x1234_5678_9abc_def0,
+        u64::MAX as u128,
+        0x8000_0000_0000_0000_0000_0000_0000_0001,
+        u128::MAX,
+    ]
+    .iter()
+    .map(|&n| BigUint::from(n))
+    .chain([(BigUint::one() << 200u32) - 1u32, BigUint::one() << 128u32])
+    .collect();
+
+    for a in &values {
+        for b in &values {
+            check(a, b);
+        }
+    }
+}
@@ -0,0 +1,452 @@
+mod der {
+    use num_bigint::{BigInt, BigUint, DecodeErrorKind};
+    use num_traits::{One, Pow};
+
+    // Vectors from X.690 and common X.509 / PKCS#1 usage
+    const VECTORS: &[(i64, &[u8])] = &[
+        (0, &[0x02, 0x01, 0x00]),
+        (1, &[0x02, 0x01, 0x01]),
+        (127, &[0x02, 0x01, 0x7f]),
+        (128, &[0x02, 0x02, 0x00, 0x80]),
+        (256, &[0x02, 0x02, 0x01, 0x00]),
+        (65537, &[0x02, 0x03, 0x01, 0x00, 0x01]),
+        (-1, &[0x02, 0x01, 0xff]),
+        (-128, &[0x02, 0x01, 0x80]),
+        (-129, &[0x02, 0x02, 0xff, 0x7f]),
+        (-256, &[0x02, 0x02, 0xff, 0x00]),
+        (
+            i64::MIN,
+            &[0x02, 0x08, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(n, der) in VECTORS {
+            let i = BigInt::from(n);
+            assert_eq!(i.to_der_integer(), der, "{}", n);
+            assert_eq!(BigInt::from_der_integer(der), Ok(i.clone()));
+
+            if n >= 0 {
+                let u = i.to_biguint().unwrap();
+                assert_eq!(u.to_der_integer(), der, "{}", n);
+                assert_eq!(BigUint::from_der_integer(der), Ok(u));
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_length() {
+        // 2^1023 needs 129 content octets, so the length takes the long form.
+        let n = BigUint::from(2u32).pow(1023u32);
+        let der = n.to_der_integer();
+        assert_eq!(&der[..5], &[0x02, 0x81, 0x81, 0x00, 0x80]);
+        assert_eq!(der.len(), 3 + 129);
+        assert_eq!(BigUint::from_der_integer(&der), Ok(n.clone()));
+
+        let i = -BigInt::from(n) - BigInt::one();
+        let der = i.to_der_integer();
+        assert_eq!(&der[..5], &[0x02, 0x81, 0x81, 0xff, 0x7f]);
+        assert_eq!(BigInt::from_der_integer(&der), Ok(i));
+    }
+
+    #[test]
+    fn test_errors() {
+        let check = |der: &[u8], kind: DecodeErrorKind| {
+            let err = BigInt::from_der_integer(der).unwrap_err();
+            assert_eq!(err.kind(), kind, "{:x?}", der);
+        };
+        check(&[], DecodeErrorKind::Truncated);
+        check(&[0x02], DecodeErrorKind::Truncated);
+        check(&[0x02, 0x02, 0x01], DecodeErrorKind::Truncated);
+        check(&[0x02, 0x82, 0x01], DecodeErrorKind::Truncated);
+        check(&[0x03, 0x01, 0x00], DecodeErrorKind::UnexpectedTag);
+        check(&[0x02, 0x00], DecodeErrorKind::InvalidLength);
+        check(
+            &[0x02, 0x80, 0x01, 0x00, 0x00],
+            DecodeErrorKind::InvalidLength,
+        );
+        check(&[0x02, 0x01, 0x00, 0x00], DecodeErrorKind::TrailingData);
+        check(&[0x02, 0x02, 0x00, 0x7f], DecodeErrorKind::NonMinimal);
+        check(&[0x02, 0x02, 0xff, 0x80], DecodeErrorKind::NonMinimal);
+        check(&[0x02, 0x81, 0x01, 0x01], DecodeErrorKind::NonMinimal);
+        check(&[0x02, 0x82, 0x00, 0x01, 0x01], DecodeErrorKind::NonMinimal);
+
+        // A length of more octets than `usize` is invalid, not truncated.
+        check(
+            &[0x02, 0x89, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+            DecodeErrorKind::InvalidLength,
+        );
+
+        let err = BigUint::from_der_integer(&[0x02, 0x01, 0xff]).unwrap_err();
+        assert_eq!(err.kind(), DecodeErrorKind::Negative);
+    }
+}
+
+mod ssh {
+    use num_bigint::{BigInt, BigUint, DecodeErrorKind};
+
+    // Vectors from RFC 4251 section 5
+    const VECTORS: &[(i64, &[u8])] = &[
+        (0, &[0, 0, 0, 0]),
+        (
+            0x9a378f9b2e332a7,
+            &[0, 0, 0, 8, 0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7],
+        ),
+        (0x80, &[0, 0, 0, 2, 0x00, 0x80]),
+        (-0x1234, &[0, 0, 0, 2, 0xed, 0xcc]),
+        (-0xdeadbeef, &[0, 0, 0, 5, 0xff, 0x21, 0x52, 0x41, 0x11]),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for &(n, mpint) in VECTORS {
+            let i = BigInt::from(n);
+            assert_eq!(i.to_ssh_mpint(), mpint, "{:x}", n);
+            assert_eq!(BigInt::from_ssh_mpint(mpint), Ok((i.clone(), &[][..])));
+            assert_eq!(
+                BigInt::from_ssh_mpint_lenient(mpint),
+                Ok((i.clone(), &[][..]))
+            );
+
+            if n >= 0 {
+                let u = i.to_biguint().unwrap();
+                assert_eq!(u.to_ssh_mpint(), mpint, "{:x}", n);
+                assert_eq!(BigUint::from_ssh_mpint(mpint), Ok((u, &[][..])));
+            } else {
+                let err = BigUint::from_ssh_mpint(mpint).unwrap_err();
+                assert_eq!(err.kind(), DecodeErrorKind::Negative);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rest() {
+        let bytes = [0, 0, 0, 1, 0x7f, 0, 0, 0, 0, 0xaa];
+        let (a, rest) = BigInt::from_ssh_mpint(&bytes).unwrap();
+        let (b, rest) = BigInt::from_ssh_mpint(rest).unwrap();
+        assert_eq!(
+            (a, b, rest),
+            (BigInt::from(0x7f), BigInt::from(0), &[0xaa][..])
+        );
+    }
+
+    #[test]
+    fn test_non_minimal() {
+        let cases: &[(&[u8], i64)] = &[
+            (&[0, 0, 0, 1, 0x00], 0),
+            (&[0, 0, 0, 2, 0x00, 0x7f], 0x7f),
+            (&[0, 0, 0, 3, 0x00, 0x00, 0x80], 0x80),
+            (&[0, 0, 0, 2, 0xff, 0x80], -0x80),
+        ];
+        for &(mpint, n) in cases {
+            let err = BigInt::from_ssh_mpint(mpint).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::NonMinimal, "{:x?}", mpint);
+            let (i, _) = BigInt::from_ssh_mpint_lenient(mpint).unwrap();
+            assert_eq!(i, BigInt::from(n));
+        }
+
+        let (u, _) = BigUint::from_ssh_mpint_lenient(&[0, 0, 0, 2, 0x00, 0x01]).unwrap();
+        assert_eq!(u, BigUint::from(1u32));
+    }
+
+    #[test]
+    fn test_truncated() {
+        // Any 32-bit length fits in `usize`, so even the largest is just short of input.
+        let longest = [0xff, 0xff, 0xff, 0xff, 0x01];
+        for mpint in [&[][..], &[0, 0, 0], &[0, 0, 0, 2, 0x01], &longest] {
+            let err = BigInt::from_ssh_mpint_lenient(mpint).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::Truncated);
+        }
+    }
+}
+
+mod pgp {
+    use num_bigint::{BigUint, DecodeErrorKind};
+    use num_traits::{One, Pow};
+
+    #[test]
+    fn test_vectors() {
+        // Examples from RFC 4880 section 3.2
+        let vectors: &[(u32, &[u8])] = &[
+            (0, &[0, 0]),
+            (1, &[0, 1, 0x01]),
+            (511, &[0, 9, 0x01, 0xff]),
+            (0x8000, &[0, 16, 0x80, 0x00]),
+        ];
+        for &(n, mpi) in vectors {
+            let n = BigUint::from(n);
+            assert_eq!(n.to_pgp_mpi().unwrap(), mpi);
+            assert_eq!(BigUint::from_pgp_mpi(mpi), Ok((n.clone(), &[][..])));
+            assert_eq!(BigUint::from_pgp_mpi_lenient(mpi), Ok((n, &[][..])));
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let max = BigUint::from(2u32).pow(65535u32) - BigUint::one();
+        let mpi = max.to_pgp_mpi().unwrap();
+        assert_eq!(&mpi[..3], &[0xff, 0xff, 0x7f]);
+        assert_eq!(mpi.len(), 2 + 8192);
+        assert_eq!(BigUint::from_pgp_mpi(&mpi), Ok((max.clone(), &[][..])));
+
+        assert_eq!((max + 1u32).to_pgp_mpi(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        // Bit counts that are too big or too small for the value
+        for mpi in [&[0, 10, 0x01, 0xff][..], &[0, 8, 0x01], &[0, 1, 0x00]] {
+            let err = BigUint::from_pgp_mpi(mpi).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::NonMinimal, "{:x?}", mpi);
+        }
+        assert_eq!(
+            BigUint::from_pgp_mpi_lenient(&[0, 10, 0x01, 0xff]),
+            Ok((BigUint::from(511u32), &[][..]))
+        );
+
+        for mpi in [&[][..], &[0], &[0, 9, 0x01]] {
+            let err = BigUint::from_pgp_mpi_lenient(mpi).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::Truncated);
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+mod cbor {
+    use num_bigint::{BigInt, BigUint, DecodeErrorKind};
+    use num_traits::One;
+
+    fn two_64() -> BigInt {
+        BigInt::one() << 64u32
+    }
+
+    #[test]
+    fn test_vectors() {
+        // Examples from RFC 8949 appendix A
+        let cases: &[(BigInt, &[u8])] = &[
+            (two_64(), &[0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+            (-two_64() - 1, &[0xc3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        for (i, cbor) in cases {
+            assert_eq!(&i.to_cbor(), cbor);
+            assert_eq!(BigInt::from_cbor(cbor), Ok((i.clone(), &[][..])));
+        }
+
+        let n = two_64().to_biguint().unwrap();
+        assert_eq!(n.to_cbor(), cases[0].1);
+        assert_eq!(BigUint::from_cbor(cases[0].1), Ok((n, &[][..])));
+    }
+
+    #[test]
+    fn test_small() {
+        let cases: &[(i64, &[u8])] = &[
+            (0, &[0xc2, 0x40]),
+            (1, &[0xc2, 0x41, 0x01]),
+            (-1, &[0xc3, 0x40]),
+            (-256, &[0xc3, 0x41, 0xff]),
+            (-257, &[0xc3, 0x42, 0x01, 0x00]),
+        ];
+        for &(n, cbor) in cases {
+            let i = BigInt::from(n);
+            assert_eq!(i.to_cbor(), cbor, "{}", n);
+            assert_eq!(BigInt::from_cbor(cbor), Ok((i, &[][..])));
+        }
+    }
+
+    #[test]
+    fn test_plain_integers() {
+        let cases: &[(&[u8], i128)] = &[
+            (&[0x00], 0),
+            (&[0x17], 23),
+            (&[0x18, 0x18], 24),
+            (&[0x19, 0x03, 0xe8], 1000),
+            (
+                &[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                u64::MAX.into(),
+            ),
+            (&[0x20], -1),
+            (&[0x38, 0x63], -100),
+            (
+                &[0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                -1 - i128::from(u64::MAX),
+            ),
+        ];
+        for &(cbor, n) in cases {
+            assert_eq!(BigInt::from_cbor(cbor), Ok((BigInt::from(n), &[][..])));
+            if n >= 0 {
+                let (u, _) = BigUint::from_cbor(cbor).unwrap();
+                assert_eq!(u, BigUint::from(n as u128));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        // Leading zeros are allowed, and the rest of the input is returned.
+        let (i, rest) = BigInt::from_cbor(&[0xc2, 0x43, 0x00, 0x01, 0x00, 0xf6]).unwrap();
+        assert_eq!((i, rest), (BigInt::from(256), &[0xf6][..]));
+
+        let check = |cbor: &[u8], kind: DecodeErrorKind| {
+            let err = BigInt::from_cbor(cbor).unwrap_err();
+            assert_eq!(err.kind(), kind, "{:x?}", cbor);
+        };
+        check(&[], DecodeErrorKind::Truncated);
+        check(&[0x19, 0x01], DecodeErrorKind::Truncated);
+        check(&[0xc2], DecodeErrorKind::Truncated);
+        check(&[0xc2, 0x42, 0x01], DecodeErrorKind::Truncated);
+        check(&[0x41, 0x01], DecodeErrorKind::UnexpectedTag);
+        check(&[0xc4, 0x41, 0x01], DecodeErrorKind::UnexpectedTag);
+        check(&[0xc2, 0x01], DecodeErrorKind::UnexpectedTag);
+        check(&[0x1f], DecodeErrorKind::InvalidLength);
+        check(
+            &[0xc2, 0x5f, 0x41, 0x01, 0xff],
+            DecodeErrorKind::InvalidLength,
+        );
+        check(
+            &[
+                0xc2, 0x5b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+            ],
+            DecodeErrorKind::InvalidLength,
+        );
+
+        for cbor in [&[0x20][..], &[0xc3, 0x40]] {
+            let err = BigUint::from_cbor(cbor).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::Negative);
+        }
+    }
+}
+
+mod leb128 {
+    use num_bigint::{BigInt, BigUint, DecodeErrorKind};
+    use num_traits::{One, Pow};
+
+    #[test]
+    fn test_unsigned() {
+        // Examples from the DWARF 5 specification, figure 22
+        let cases: &[(u32, &[u8])] = &[
+            (0, &[0x00]),
+            (2, &[0x02]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x01]),
+            (129, &[0x81, 0x01]),
+            (130, &[0x82, 0x01]),
+            (12857, &[0xb9, 0x64]),
+            (624485, &[0xe5, 0x8e, 0x26]),
+        ];
+        for &(n, leb) in cases {
+            let n = BigUint::from(n);
+            let mut out = vec![0xaa];
+            n.write_uleb128(&mut out);
+            assert_eq!(&out[1..], leb);
+            assert_eq!(BigUint::read_uleb128(leb), Ok((n, &[][..])));
+        }
+    }
+
+    #[test]
+    fn test_signed() {
+        // Examples from the DWARF 5 specification, figure 23
+        let cases: &[(i32, &[u8])] = &[
+            (0, &[0x00]),
+            (2, &[0x02]),
+            (-2, &[0x7e]),
+            (63, &[0x3f]),
+            (-64, &[0x40]),
+            (64, &[0xc0, 0x00]),
+            (-65, &[0xbf, 0x7f]),
+            (127, &[0xff, 0x00]),
+            (-127, &[0x81, 0x7f]),
+            (128, &[0x80, 0x01]),
+            (-128, &[0x80, 0x7f]),
+            (129, &[0x81, 0x01]),
+            (-129, &[0xff, 0x7e]),
+            (-123456, &[0xc0, 0xbb, 0x78]),
+        ];
+        for &(i, leb) in cases {
+            let i = BigInt::from(i);
+            let mut out = Vec::new();
+            i.write_sleb128(&mut out);
+            assert_eq!(out, leb, "{}", i);
+            assert_eq!(BigInt::read_sleb128(leb), Ok((i, &[][..])));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for bits in 0u32..300 {
+            let p = BigUint::from(2u32).pow(bits);
+            for n in [&p - 1u32, p.clone(), &p + 1u32] {
+                let mut out = Vec::new();
+                n.write_uleb128(&mut out);
+                assert_eq!(out.len() as u64, Ord::max(1, (n.bits() + 6) / 7));
+                assert_eq!(BigUint::read_uleb128(&out), Ok((n.clone(), &[][..])));
+
+                for i in [BigInt::from(n.clone()), -BigInt::from(n)] {
+                    let mut out = Vec::new();
+                    i.write_sleb128(&mut out);
+                    assert_eq!(BigInt::read_sleb128(&out), Ok((i.clone(), &[][..])));
+                    assert_eq!(BigInt::from_zigzag(&i.to_zigzag()), i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        // Padding with redundant groups
+        assert_eq!(
+            BigUint::read_uleb128(&[0x85, 0x80, 0x00, 0x01]),
+            Ok((BigUint::from(5u32), &[0x01][..]))
+        );
+        assert_eq!(
+            BigInt::read_sleb128(&[0xff, 0xff, 0x7f]),
+            Ok((-BigInt::one(), &[][..]))
+        );
+
+        for leb in [&[][..], &[0x80], &[0xff, 0xff]] {
+            let err = BigUint::read_uleb128(leb).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::Truncated);
+            let err = BigInt::read_sleb128(leb).unwrap_err();
+            assert_eq!(err.kind(), DecodeErrorKind::Truncated);
+        }
+    }
+
+    #[test]
+    fn test_zigzag() {
+        for (i, z) in [
+            (0, 0u32),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (2, 4),
+            (i32::MIN, u32::MAX),
+        ] {
+            let i = BigInt::from(i);
+            let z = BigUint::from(z);
+            assert_eq!(i.to_zigzag(), z);
+            assert_eq!(BigInt::from_zigzag(&z), i);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io() {
+        use std::io::{Cursor, ErrorKind, Read};
+
+        let mut out = Vec::new();
+        BigUint::from(624485u32).write_uleb128_io(&mut out).unwrap();
+        BigInt::from(-123456).write_sleb128_io(&mut out).unwrap();
+        out.push(0xaa);
+
+        let mut reader = Cursor::new(out);
+        let n = BigUint::read_uleb128_io(&mut reader).unwrap();
+        let i = BigInt::read_sleb128_io(&mut reader).unwrap();
+        assert_eq!((n, i), (BigUint::from(624485u32), BigInt::from(-123456)));
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, [0xaa]);
+
+        let err = BigUint::read_uleb128_io(&[0x80, 0x80][..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
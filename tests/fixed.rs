@@ -0,0 +1,255 @@
+use num_bigint::{FixedBigInt, FixedBigUint, Sign};
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+
+/// The number of bits in a word.
+const WORD: u32 = 64;
+
+type Uint = FixedBigUint<8>;
+type Int = FixedBigInt<8>;
+
+fn uint(n: u128) -> Uint {
+    Uint::from_u128(n).unwrap()
+}
+
+fn int(n: i128) -> Int {
+    Int::from_i128(n).unwrap()
+}
+
+/// Values spread across the whole capacity, including both extremes.
+fn values() -> Vec<Uint> {
+    let mut values = vec![
+        Uint::zero(),
+        Uint::one(),
+        uint(u64::MAX.into()),
+        uint(u128::MAX),
+        Uint::MAX,
+        Uint::MAX >> 1,
+        Uint::one() << (Uint::BITS as u32 - 1),
+        uint(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210) << (WORD + 3),
+    ];
+    let three = uint(3);
+    let mut power = three;
+    while let Some(next) = power
+        .checked_mul(&power)
+        .and_then(|p| p.checked_mul(&three))
+    {
+        values.push(next);
+        power = next;
+    }
+    values
+}
+
+#[test]
+fn test_capacity() {
+    assert_eq!(Uint::BITS, 8 * u64::from(WORD));
+    assert_eq!(FixedBigUint::<1>::BITS, 64);
+    assert_eq!(FixedBigInt::<4>::MAX.bits(), 256);
+    assert_eq!(Uint::MAX.bits(), Uint::BITS);
+    assert_eq!(Uint::zero().bits(), 0);
+    assert_eq!(Uint::MAX.checked_add(&Uint::one()), None);
+    assert_eq!(Uint::zero().checked_sub(&Uint::one()), None);
+    assert_eq!(
+        (Uint::MAX >> 1).checked_mul(&uint(2)),
+        Some(Uint::MAX - Uint::one())
+    );
+    assert_eq!((Uint::MAX >> 1).checked_mul(&uint(3)), None);
+    assert_eq!(Uint::one().checked_div(&Uint::zero()), None);
+    assert_eq!(Uint::one().checked_rem(&Uint::zero()), None);
+
+    assert_eq!(FixedBigUint::<1>::from_u128(u128::MAX), None);
+    assert_eq!(uint(u128::MAX).to_u128(), Some(u128::MAX));
+    assert_eq!((uint(u128::MAX) << 1).to_u128(), None);
+    assert_eq!(uint(5).to_i64(), Some(5));
+    assert_eq!(Uint::from_i64(-1), None);
+
+    let bytes: Vec<u8> = (1..=32).collect();
+    let be = Uint::from_bytes_be(&bytes).unwrap();
+    let le = Uint::from_bytes_le(&bytes).unwrap();
+    assert_eq!(be.bits(), 32 * 8 - 7);
+    assert_eq!(le.bits(), 32 * 8 - 2);
+    assert_eq!(
+        format!("{:x}", be),
+        "102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
+    );
+    assert_eq!(Uint::from_bytes_le(&[0xff; 80]), None);
+    assert_eq!(Uint::from_bytes_le(&[0; 80]), Some(Uint::zero()));
+}
+
+#[test]
+fn test_ops() {
+    let values = values();
+    for &a in &values {
+        for &b in &values {
+            if let Some(sum) = a.checked_add(&b) {
+                assert_eq!(sum - b, a);
+                assert_eq!(sum.checked_sub(&a), Some(b));
+            }
+            if let Some(product) = a.checked_mul(&b) {
+                if !b.is_zero() {
+                    assert_eq!(product / b, a);
+                    assert_eq!(product % b, Uint::zero());
+                }
+            }
+            if !b.is_zero() {
+                let (q, r) = a.div_rem(&b);
+                assert!(r < b);
+                assert_eq!(q * b + r, a);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_shift() {
+    let n = uint(0xdead_beef);
+    assert_eq!(n << 0, n);
+    assert_eq!((n << (2 * WORD + 4)) >> (2 * WORD + 4), n);
+    assert_eq!(
+        n << (8 * WORD - 8),
+        Uint::from_u64(0xef).unwrap() << (8 * WORD - 8)
+    );
+    assert_eq!(Uint::MAX >> (8 * WORD - 1), Uint::one());
+    let mut m = n;
+    m <<= WORD;
+    m >>= WORD + 4;
+    assert_eq!(m, uint(0xdeadbee));
+}
+
+#[test]
+#[should_panic(expected = "attempt to shift left with overflow")]
+fn test_shift_overflow() {
+    let _ = Uint::one() << (8 * WORD);
+}
+
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn test_add_overflow() {
+    let _ = Uint::MAX + Uint::one();
+}
+
+#[test]
+fn test_fmt() {
+    let n = uint(1234567890123456789012345678901234567);
+    assert_eq!(n.to_string(), "1234567890123456789012345678901234567");
+    assert_eq!(format!("{:?}", Uint::zero()), "0");
+    assert_eq!(
+        format!("{:#x}", uint(0xabc) << WORD),
+        format!("{:#x}", 0xabc_u128 << WORD)
+    );
+    assert_eq!(format!("{:X}", uint(0xabc)), "ABC");
+    assert_eq!(format!("{:b}", uint(5)), "101");
+    assert_eq!(
+        format!("{:>6}|{:<6}|{:^6}", uint(42), uint(42), uint(42)),
+        "    42|42    |  42  "
+    );
+    assert_eq!(format!("{:+08}", uint(42)), "+0000042");
+    assert_eq!(format!("{:#010x}", uint(255)), "0x000000ff");
+    assert_eq!(format!("{:*^7}", int(-42)), "**-42**");
+    assert_eq!(format!("{:08}", int(-42)), "-0000042");
+
+    // Build `2^BITS` by doubling a decimal string, then subtract one.
+    let mut max = String::from("1");
+    for _ in 0..Uint::BITS {
+        let mut carry = 0;
+        let digits: String = (max.bytes().rev())
+            .map(|b| {
+                let d = (b - b'0') * 2 + carry;
+                carry = d / 10;
+                char::from(b'0' + d % 10)
+            })
+            .collect();
+        max = digits.chars().rev().collect();
+        if carry > 0 {
+            max.insert(0, '1');
+        }
+    }
+    let last = max.pop().unwrap();
+    max.push(char::from(last as u8 - 1));
+    assert_eq!(Uint::MAX.to_string(), max);
+}
+
+#[test]
+fn test_signed() {
+    assert_eq!(-Int::MAX, Int::MIN);
+    assert_eq!(Int::MIN.checked_sub(&Int::one()), None);
+    assert_eq!(Int::MAX.checked_add(&Int::one()), None);
+    assert_eq!(Int::MAX.checked_add(&Int::MIN), Some(Int::zero()));
+    assert_eq!(Int::new(Sign::Minus, Uint::zero()).sign(), Sign::NoSign);
+    assert_eq!(Int::new(Sign::NoSign, Uint::one()), Int::zero());
+    assert_eq!(*int(-5).magnitude(), uint(5));
+    assert_eq!(Int::from(uint(5)), int(5));
+    assert_eq!(int(i128::MIN).to_i128(), Some(i128::MIN));
+    assert_eq!(int(-1).to_u64(), None);
+    assert_eq!((int(i128::MIN) - int(1)).to_i128(), None);
+    assert_eq!(int(-1).bits(), 1);
+
+    let values = [-1000, -7, -2, -1, 0, 1, 2, 7, 1000];
+    for &a in &values {
+        for &b in &values {
+            assert_eq!(int(a) + int(b), int(a + b));
+            assert_eq!(int(a) - int(b), int(a - b));
+            assert_eq!(int(a) * int(b), int(a * b));
+            assert_eq!(int(a).cmp(&int(b)), a.cmp(&b));
+            if b != 0 {
+                assert_eq!(int(a) / int(b), int(a / b));
+                assert_eq!(int(a) % int(b), int(a % b));
+                assert_eq!(int(a).div_rem(&int(b)), (int(a / b), int(a % b)));
+            }
+        }
+        assert_eq!(-int(a), int(-a));
+        assert_eq!(int(a).to_string(), a.to_string());
+        assert_eq!(
+            format!("{:#x}", int(a)),
+            format!("{}{:#x}", if a < 0 { "-" } else { "" }, a.unsigned_abs())
+        );
+    }
+}
+
+#[test]
+fn test_biguint_conversion() {
+    use num_bigint::{BigInt, BigUint};
+
+    let values = values();
+    for &a in &values {
+        let big = BigUint::from(a);
+        assert_eq!(big.bits(), a.bits());
+        assert_eq!(big.to_string(), a.to_string());
+        assert_eq!(Uint::try_from(&big), Ok(a));
+        assert_eq!(Uint::try_from(big.clone()), Ok(a));
+        for &b in &values {
+            let big_b = BigUint::from(b);
+            let sum = &big + &big_b;
+            assert_eq!(
+                a.checked_add(&b).map(BigUint::from),
+                Some(sum).filter(|s| s.bits() <= Uint::BITS)
+            );
+            let product = &big * &big_b;
+            assert_eq!(
+                a.checked_mul(&b).map(BigUint::from),
+                Some(product).filter(|p| p.bits() <= Uint::BITS)
+            );
+            if !b.is_zero() {
+                assert_eq!(BigUint::from(a / b), &big / &big_b);
+                assert_eq!(BigUint::from(a % b), &big % &big_b);
+            }
+
+            let (x, y) = (Int::from(a), -Int::from(b));
+            let (big_x, big_y) = (BigInt::from(x), BigInt::from(y));
+            assert_eq!(x.checked_add(&y).map(BigInt::from), Some(&big_x + &big_y));
+            if !b.is_zero() {
+                assert_eq!(BigInt::from(x / y), &big_x / &big_y);
+                assert_eq!(BigInt::from(x % y), &big_x % &big_y);
+            }
+            assert_eq!(Int::try_from(&big_y), Ok(y));
+        }
+    }
+
+    let too_big = BigUint::from(Uint::MAX) + 1u32;
+    assert!(Uint::try_from(&too_big).is_err());
+    assert_eq!(
+        Uint::try_from(too_big.clone()).unwrap_err().into_original(),
+        too_big
+    );
+    assert!(Int::try_from(-BigInt::from(too_big)).is_err());
+}